@@ -1,16 +1,24 @@
 use std::os::raw::c_char;
 
 use crate::api::services::{
+    clock_service::{advance_all_npc_time, advance_npc_time, format_game_time_json, get_npc_time, set_npc_time},
     evaluator_service::{
-        create_npc_session as create_session, evaluate_interaction_with_cached_model, format_emotion_json,
-        initialize_shared_model, remove_npc_session as remove_session, with_npc_evaluator,
+        create_npc_session as create_session, evaluate_event_json, evaluate_interaction_with_cached_model,
+        evaluate_interactions_json, explain_interaction_with_cached_model, format_affect_state_json,
+        format_labeled_emotion_json, format_time_to_baseline_json, initialize_shared_model,
+        initialize_shared_model_with_options, labeled_emotion_json, observe_interaction_with_cached_model,
+        parse_texts_json, preview_interactions_with_cached_model, rank_candidates_json,
+        remove_npc_session as remove_session, with_npc_evaluator,
     },
-    memory_service::{clear_memory, get_all_memory, import_memory},
+    memory_service::{clear_memory, get_all_memory, get_memory_snapshot, import_memory},
+    social_service::{run_contagion_json, share_gossip},
+    source_service::{get_source_json, register_source_json},
+    trigger_service::{check_npc_triggers, poll_events_json, register_trigger_json},
     validation_service::{parse_c_string, parse_optional_c_string},
 };
 use crate::api::types::ApiResult;
-use crate::MemoryStore;
-use crate::{MemoryEmotionEvaluator, NpcConfig};
+use crate::{AffectStore, GameTime, MemoryStore, Relationship, TriggerStore};
+use crate::{MemoryEmotionEvaluator, NpcConfig, PredictorOptions};
 
 #[no_mangle]
 pub extern "C" fn initialize_neural_matrix() -> *mut ApiResult {
@@ -22,6 +30,26 @@ pub extern "C" fn initialize_neural_matrix() -> *mut ApiResult {
     }
 }
 
+#[no_mangle]
+pub extern "C" fn initialize_neural_matrix_with_options(options_json: *const c_char) -> *mut ApiResult {
+    let options_str = match parse_c_string(options_json, "Options string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let options: PredictorOptions = match serde_json::from_str(&options_str) {
+        Ok(o) => o,
+        Err(e) => return Box::into_raw(Box::new(ApiResult::error(format!("Failed to parse options: {}", e)))),
+    };
+
+    match initialize_shared_model_with_options(&options) {
+        Ok(()) => Box::into_raw(Box::new(ApiResult::success(
+            "Model initialized successfully".to_string(),
+        ))),
+        Err(result) => result,
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn create_npc_session(config_json: *const c_char, npc_memory_json: *const c_char) -> *mut ApiResult {
     let npc_id = uuid::Uuid::new_v4().to_string();
@@ -31,11 +59,15 @@ pub extern "C" fn create_npc_session(config_json: *const c_char, npc_memory_json
         Err(result) => return result,
     };
 
-    let config: NpcConfig = match serde_json::from_str(&config_str) {
-        Ok(c) => c,
+    let config: NpcConfig = match serde_json::from_str::<NpcConfig>(&config_str) {
+        Ok(c) => c.resolved(),
         Err(e) => return Box::into_raw(Box::new(ApiResult::error(format!("Failed to parse config: {}", e)))),
     };
 
+    if let Err(e) = config.validate() {
+        return Box::into_raw(Box::new(ApiResult::error(format!("Invalid config: {}", e))));
+    }
+
     if !npc_memory_json.is_null() {
         if let Err(result) = import_memory(&npc_id, npc_memory_json) {
             return result;
@@ -78,6 +110,20 @@ pub extern "C" fn remove_npc_session(npc_id: *const c_char) -> *mut ApiResult {
         ))));
     }
 
+    if let Err(e) = AffectStore::remove_npc(&npc_id_str) {
+        return Box::into_raw(Box::new(ApiResult::error(format!(
+            "Failed to remove NPC affect state: {}",
+            e
+        ))));
+    }
+
+    if let Err(e) = TriggerStore::remove_npc(&npc_id_str) {
+        return Box::into_raw(Box::new(ApiResult::error(format!(
+            "Failed to remove NPC triggers: {}",
+            e
+        ))));
+    }
+
     if let Err(result) = remove_session(&npc_id_str) {
         return result;
     }
@@ -107,9 +153,266 @@ pub extern "C" fn evaluate_interaction(
     let source_str = parse_optional_c_string(source_id);
 
     with_npc_evaluator(&npc_id_str, |evaluator| {
-        let final_emotion = evaluate_interaction_with_cached_model(evaluator, &text_str, source_str.as_deref())?;
+        let final_emotion = evaluate_interaction_with_cached_model(evaluator, &text_str, source_str.as_deref(), None)?;
+
+        Ok(format_labeled_emotion_json(
+            &final_emotion,
+            &evaluator.label_emotion(&final_emotion),
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn evaluate_interactions(interactions_json: *const c_char) -> *mut ApiResult {
+    let interactions_str = match parse_c_string(interactions_json, "Interactions string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    match evaluate_interactions_json(&interactions_str) {
+        Ok(json) => Box::into_raw(Box::new(ApiResult::success(json))),
+        Err(result) => result,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn evaluate_interaction_at(
+    npc_id: *const c_char,
+    text: *const c_char,
+    source_id: *const c_char,
+    game_time: GameTime,
+) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let text_str = match parse_c_string(text, "Text string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = parse_optional_c_string(source_id);
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let final_emotion =
+            evaluate_interaction_with_cached_model(evaluator, &text_str, source_str.as_deref(), Some(game_time))?;
+
+        Ok(format_labeled_emotion_json(
+            &final_emotion,
+            &evaluator.label_emotion(&final_emotion),
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn preview_interaction(
+    npc_id: *const c_char,
+    text: *const c_char,
+    source_id: *const c_char,
+) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let text_str = match parse_c_string(text, "Text string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = parse_optional_c_string(source_id);
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let previews = preview_interactions_with_cached_model(evaluator, &[text_str], source_str.as_deref())?;
+
+        Ok(format_labeled_emotion_json(
+            &previews[0],
+            &evaluator.label_emotion(&previews[0]),
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn preview_interactions(
+    npc_id: *const c_char,
+    texts_json: *const c_char,
+    source_id: *const c_char,
+) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let texts_str = match parse_c_string(texts_json, "Texts string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = parse_optional_c_string(source_id);
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let texts = parse_texts_json(&texts_str)?;
+        let previews = preview_interactions_with_cached_model(evaluator, &texts, source_str.as_deref())?;
+
+        let results: Vec<serde_json::Value> = texts
+            .iter()
+            .zip(&previews)
+            .map(|(text, emotion)| {
+                let mut json = labeled_emotion_json(emotion, &evaluator.label_emotion(emotion));
+                json["text"] = serde_json::json!(text);
+                json
+            })
+            .collect();
+
+        Ok(serde_json::Value::Array(results).to_string())
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn rank_candidate_lines(npc_id: *const c_char, request_json: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let request_str = match parse_c_string(request_json, "Rank request string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| rank_candidates_json(evaluator, &request_str))
+}
+
+#[no_mangle]
+pub extern "C" fn explain_interaction(
+    npc_id: *const c_char,
+    text: *const c_char,
+    source_id: *const c_char,
+    top_memories: u32,
+) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let text_str = match parse_c_string(text, "Text string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = parse_optional_c_string(source_id);
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        explain_interaction_with_cached_model(evaluator, &text_str, source_str.as_deref(), top_memories as usize)
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn observe_interaction(
+    npc_id: *const c_char,
+    text: *const c_char,
+    source_id: *const c_char,
+    target_id: *const c_char,
+) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
 
-        Ok(format_emotion_json(&final_emotion))
+    let text_str = match parse_c_string(text, "Text string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = match parse_c_string(source_id, "Source ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let target_str = match parse_c_string(target_id, "Target ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let final_emotion =
+            observe_interaction_with_cached_model(evaluator, &text_str, &source_str, &target_str, None)?;
+
+        Ok(format_labeled_emotion_json(
+            &final_emotion,
+            &evaluator.label_emotion(&final_emotion),
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn evaluate_event(npc_id: *const c_char, event_json: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let event_str = match parse_c_string(event_json, "Event string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let final_emotion = evaluate_event_json(evaluator, &event_str)?;
+
+        Ok(format_labeled_emotion_json(
+            &final_emotion,
+            &evaluator.label_emotion(&final_emotion),
+        ))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn advance_time(npc_id: *const c_char, minutes: GameTime) -> *mut ApiResult {
+    let npc_id_str = match parse_optional_c_string(npc_id) {
+        Some(s) => s,
+        None => {
+            return match advance_all_npc_time(minutes) {
+                Ok(count) => Box::into_raw(Box::new(ApiResult::success(
+                    serde_json::json!({ "advanced_npcs": count }).to_string(),
+                ))),
+                Err(result) => result,
+            }
+        }
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let game_time = advance_npc_time(&evaluator.npc_id, minutes)?;
+        check_npc_triggers(evaluator)?;
+        Ok(format_game_time_json(&evaluator.npc_id, game_time))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn set_game_time(npc_id: *const c_char, game_time: GameTime) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let game_time = set_npc_time(&evaluator.npc_id, game_time)?;
+        check_npc_triggers(evaluator)?;
+        Ok(format_game_time_json(&evaluator.npc_id, game_time))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn get_game_time(npc_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let game_time = get_npc_time(&evaluator.npc_id)?;
+        Ok(format_game_time_json(&evaluator.npc_id, game_time))
     })
 }
 
@@ -124,7 +427,10 @@ pub extern "C" fn get_current_emotion(npc_id: *const c_char) -> *mut ApiResult {
         let emotion = evaluator
             .calculate_current_emotion()
             .map_err(|e| format!("Failed to calculate emotion: {:?}", e))?;
-        Ok(format_emotion_json(&emotion))
+        Ok(format_labeled_emotion_json(
+            &emotion,
+            &evaluator.label_emotion(&emotion),
+        ))
     })
 }
 
@@ -144,10 +450,185 @@ pub extern "C" fn get_current_emotion_by_source_id(npc_id: *const c_char, source
         let emotion = evaluator
             .calculate_current_emotion_towards_source(&source_str)
             .map_err(|e| format!("Failed to calculate emotion towards source: {:?}", e))?;
-        Ok(format_emotion_json(&emotion))
+        Ok(format_labeled_emotion_json(
+            &emotion,
+            &evaluator.label_emotion(&emotion),
+        ))
     })
 }
 
+#[no_mangle]
+pub extern "C" fn get_relationship(npc_id: *const c_char, source_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = match parse_c_string(source_id, "Source ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let relationship = Relationship::between(evaluator, &source_str)
+            .map_err(|e| format!("Failed to calculate relationship: {:?}", e))?;
+        serde_json::to_string(&relationship).map_err(|e| format!("Failed to serialize relationship: {}", e))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn get_emotion_and_mood(npc_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let state = evaluator
+            .current_affect_state()
+            .map_err(|e| format!("Failed to calculate emotion and mood: {:?}", e))?;
+        Ok(format_affect_state_json(evaluator, &state))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn get_time_to_baseline(npc_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        let game_time = get_npc_time(&evaluator.npc_id)?;
+        let minutes = evaluator
+            .time_to_baseline()
+            .map_err(|e| format!("Failed to calculate time to baseline: {:?}", e))?;
+        Ok(format_time_to_baseline_json(game_time, minutes))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn get_npc_config(npc_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        serde_json::to_string(&evaluator.config).map_err(|e| format!("Failed to serialize config: {}", e))
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn gossip(
+    speaker_npc_id: *const c_char,
+    listener_npc_id: *const c_char,
+    source_id: *const c_char,
+) -> *mut ApiResult {
+    let speaker_str = match parse_c_string(speaker_npc_id, "Speaker NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let listener_str = match parse_c_string(listener_npc_id, "Listener NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let source_str = match parse_c_string(source_id, "Source ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    match share_gossip(&speaker_str, &listener_str, &source_str) {
+        Ok(json) => Box::into_raw(Box::new(ApiResult::success(json))),
+        Err(result) => result,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn step_emotional_contagion(groups_json: *const c_char) -> *mut ApiResult {
+    let groups_str = match parse_c_string(groups_json, "Contagion groups string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    match run_contagion_json(&groups_str) {
+        Ok(json) => Box::into_raw(Box::new(ApiResult::success(json))),
+        Err(result) => result,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn register_source(source_json: *const c_char) -> *mut ApiResult {
+    let source_str = match parse_c_string(source_json, "Source string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    match register_source_json(&source_str) {
+        Ok(message) => Box::into_raw(Box::new(ApiResult::success(message))),
+        Err(result) => result,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn get_source(source_id: *const c_char) -> *mut ApiResult {
+    let source_str = match parse_c_string(source_id, "Source ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    match get_source_json(&source_str) {
+        Ok(json) => Box::into_raw(Box::new(ApiResult::success(json))),
+        Err(result) => result,
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn register_trigger(npc_id: *const c_char, trigger_json: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let trigger_str = match parse_c_string(trigger_json, "Trigger string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| register_trigger_json(evaluator, &trigger_str))
+}
+
+#[no_mangle]
+pub extern "C" fn remove_trigger(npc_id: *const c_char, trigger_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    let trigger_str = match parse_c_string(trigger_id, "Trigger ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    with_npc_evaluator(&npc_id_str, |evaluator| {
+        match TriggerStore::remove(&evaluator.npc_id, &trigger_str) {
+            Ok(true) => Ok(format!("Trigger '{}' removed successfully", trigger_str)),
+            Ok(false) => Err(format!("Trigger '{}' not found", trigger_str)),
+            Err(e) => Err(format!("Failed to remove trigger: {}", e)),
+        }
+    })
+}
+
+#[no_mangle]
+pub extern "C" fn poll_emotion_events() -> *mut ApiResult {
+    match poll_events_json() {
+        Ok(json) => Box::into_raw(Box::new(ApiResult::success(json))),
+        Err(result) => result,
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn get_npc_memory(npc_id: *const c_char) -> *mut ApiResult {
     let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
@@ -161,6 +642,19 @@ pub extern "C" fn get_npc_memory(npc_id: *const c_char) -> *mut ApiResult {
     }
 }
 
+#[no_mangle]
+pub extern "C" fn get_npc_snapshot(npc_id: *const c_char) -> *mut ApiResult {
+    let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
+        Ok(s) => s,
+        Err(result) => return result,
+    };
+
+    match get_memory_snapshot(&npc_id_str) {
+        Ok(json) => Box::into_raw(Box::new(ApiResult::success(json))),
+        Err(result) => result,
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn clear_npc_memory(npc_id: *const c_char) -> *mut ApiResult {
     let npc_id_str = match parse_c_string(npc_id, "NPC ID string") {
@@ -175,6 +669,7 @@ pub extern "C" fn clear_npc_memory(npc_id: *const c_char) -> *mut ApiResult {
 }
 
 #[no_mangle]
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub extern "C" fn free_api_result(result: *mut ApiResult) {
     if result.is_null() {
         return;
@@ -0,0 +1,36 @@
+use crate::api::services::evaluator_service::get_npc_sessions;
+use crate::api::services::trigger_service::check_npc_triggers;
+use crate::api::types::{ApiResult, NpcId};
+use crate::{GameClock, GameTime};
+
+pub fn advance_npc_time(npc_id: &NpcId, minutes: GameTime) -> Result<GameTime, String> {
+    GameClock::advance(npc_id, minutes).map_err(|e| format!("Failed to advance time: {}", e))
+}
+
+pub fn advance_all_npc_time(minutes: GameTime) -> Result<usize, *mut ApiResult> {
+    let sessions = get_npc_sessions()?;
+
+    for (npc_id, evaluator) in sessions.iter() {
+        advance_npc_time(npc_id, minutes)
+            .and_then(|_| check_npc_triggers(evaluator))
+            .map_err(|e| Box::into_raw(Box::new(ApiResult::error(e))))?;
+    }
+
+    Ok(sessions.len())
+}
+
+pub fn set_npc_time(npc_id: &NpcId, game_time: GameTime) -> Result<GameTime, String> {
+    GameClock::set(npc_id, game_time).map_err(|e| format!("Failed to set game time: {}", e))
+}
+
+pub fn get_npc_time(npc_id: &NpcId) -> Result<GameTime, String> {
+    GameClock::now(npc_id).map_err(|e| format!("Failed to read game time: {}", e))
+}
+
+pub fn format_game_time_json(npc_id: &NpcId, game_time: GameTime) -> String {
+    serde_json::json!({
+        "npc_id": npc_id,
+        "game_time": game_time
+    })
+    .to_string()
+}
@@ -1,5 +1,9 @@
 use crate::api::types::{ApiResult, NpcId};
-use crate::{EmotionPrediction, EmotionPredictor, MemoryEmotionEvaluator};
+use crate::{
+    AffectState, EmotionLabel, EmotionPrediction, EmotionPredictor, GameClock, GameEvent, GameTime,
+    MemoryEmotionEvaluator, PredictorOptions,
+};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex, OnceLock};
 
@@ -7,7 +11,11 @@ pub static NPC_SESSIONS: OnceLock<Mutex<HashMap<NpcId, MemoryEmotionEvaluator>>>
 pub static SHARED_MODEL: OnceLock<Arc<Mutex<EmotionPredictor>>> = OnceLock::new();
 
 pub fn initialize_shared_model() -> Result<(), *mut ApiResult> {
-    let predictor = EmotionPredictor::new().map_err(|e| {
+    initialize_shared_model_with_options(&PredictorOptions::default())
+}
+
+pub fn initialize_shared_model_with_options(options: &PredictorOptions) -> Result<(), *mut ApiResult> {
+    let predictor = EmotionPredictor::with_options(options).map_err(|e| {
         Box::into_raw(Box::new(ApiResult::error(format!(
             "Failed to initialize model: {:?}",
             e
@@ -116,10 +124,64 @@ pub fn predict_with_cached_model(text: &str) -> Result<EmotionPrediction, *mut A
         .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Prediction failed: {:?}", e)))))
 }
 
-pub fn format_emotion_json(emotion: &EmotionPrediction) -> String {
-    serde_json::json!({
+pub fn predict_texts_with_cached_model(texts: &[String]) -> Result<Vec<EmotionPrediction>, String> {
+    let model_arc = SHARED_MODEL
+        .get()
+        .ok_or_else(|| "Model not initialized. Call initialize_neural_matrix first.".to_string())?;
+
+    let mut model = model_arc
+        .lock()
+        .map_err(|_| "Failed to acquire model lock".to_string())?;
+
+    let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+
+    model
+        .predict_batch(&texts)
+        .map_err(|e| format!("Prediction failed: {:?}", e))
+}
+
+fn emotion_json(emotion: &EmotionPrediction) -> serde_json::Value {
+    let mut json = serde_json::json!({
         "valence": emotion.valence,
         "arousal": emotion.arousal
+    });
+
+    if let Some(dominance) = emotion.dominance {
+        json["dominance"] = serde_json::json!(dominance);
+    }
+
+    json
+}
+
+pub fn format_emotion_json(emotion: &EmotionPrediction) -> String {
+    emotion_json(emotion).to_string()
+}
+
+pub fn labeled_emotion_json(emotion: &EmotionPrediction, label: &EmotionLabel) -> serde_json::Value {
+    let mut json = emotion_json(emotion);
+    json["label"] = serde_json::json!(label);
+
+    json
+}
+
+pub fn format_labeled_emotion_json(emotion: &EmotionPrediction, label: &EmotionLabel) -> String {
+    labeled_emotion_json(emotion, label).to_string()
+}
+
+pub fn format_time_to_baseline_json(game_time: GameTime, minutes: Option<GameTime>) -> String {
+    serde_json::json!({
+        "game_time": game_time,
+        "minutes": minutes,
+        "baseline_at": minutes.map(|minutes| game_time + minutes)
+    })
+    .to_string()
+}
+
+pub fn format_affect_state_json(evaluator: &MemoryEmotionEvaluator, state: &AffectState) -> String {
+    serde_json::json!({
+        "emotion": labeled_emotion_json(&state.emotion, &evaluator.label_emotion(&state.emotion)),
+        "mood": labeled_emotion_json(&state.mood, &evaluator.label_emotion(&state.mood)),
+        "game_time": state.updated_at
     })
     .to_string()
 }
@@ -128,11 +190,215 @@ pub fn evaluate_interaction_with_cached_model(
     evaluator: &MemoryEmotionEvaluator,
     text: &str,
     source_id: Option<&str>,
+    game_time: Option<GameTime>,
 ) -> Result<EmotionPrediction, String> {
     let predicted_emotion =
         predict_with_cached_model(text).map_err(|_| "Failed to predict emotion with cached model".to_string())?;
 
+    let occurred_at = resolve_occurred_at(evaluator, game_time)?;
+
     evaluator
-        .evaluate_npc_emotion(text, &predicted_emotion, 0, source_id)
+        .evaluate_npc_emotion(text, &predicted_emotion, occurred_at, source_id)
         .map_err(|e| format!("Failed to evaluate interaction: {:?}", e))
 }
+
+pub fn observe_interaction_with_cached_model(
+    evaluator: &MemoryEmotionEvaluator,
+    text: &str,
+    source_id: &str,
+    target_id: &str,
+    game_time: Option<GameTime>,
+) -> Result<EmotionPrediction, String> {
+    let predicted_emotion =
+        predict_with_cached_model(text).map_err(|_| "Failed to predict emotion with cached model".to_string())?;
+
+    let occurred_at = resolve_occurred_at(evaluator, game_time)?;
+
+    evaluator
+        .observe_npc_interaction(text, &predicted_emotion, occurred_at, source_id, target_id)
+        .map_err(|e| format!("Failed to evaluate observed interaction: {:?}", e))
+}
+
+pub fn explain_interaction_with_cached_model(
+    evaluator: &MemoryEmotionEvaluator,
+    text: &str,
+    source_id: Option<&str>,
+    top_memories: usize,
+) -> Result<String, String> {
+    let predicted_emotion =
+        predict_with_cached_model(text).map_err(|_| "Failed to predict emotion with cached model".to_string())?;
+
+    let occurred_at = resolve_occurred_at(evaluator, None)?;
+
+    let breakdown = evaluator
+        .explain_npc_emotion(text, &predicted_emotion, occurred_at, source_id, top_memories)
+        .map_err(|e| format!("Failed to explain interaction: {:?}", e))?;
+
+    let mut json = serde_json::to_value(&breakdown).map_err(|e| format!("Failed to serialize breakdown: {}", e))?;
+    json["final_emotion"] = labeled_emotion_json(
+        &breakdown.final_emotion,
+        &evaluator.label_emotion(&breakdown.final_emotion),
+    );
+
+    Ok(json.to_string())
+}
+
+pub fn preview_interactions_with_cached_model(
+    evaluator: &MemoryEmotionEvaluator,
+    texts: &[String],
+    source_id: Option<&str>,
+) -> Result<Vec<EmotionPrediction>, String> {
+    let predicted_emotions = predict_texts_with_cached_model(texts)?;
+
+    evaluator
+        .preview_npc_emotions(&predicted_emotions, source_id)
+        .map_err(|e| format!("Failed to preview interactions: {:?}", e))
+}
+
+pub fn parse_texts_json(texts_json: &str) -> Result<Vec<String>, String> {
+    serde_json::from_str(texts_json).map_err(|e| format!("Failed to parse texts: {}", e))
+}
+
+#[derive(Deserialize)]
+struct InteractionRequest {
+    npc_id: NpcId,
+    text: String,
+    #[serde(default)]
+    source_id: Option<String>,
+}
+
+/// Evaluates interactions for many NPCs with a single batched inference. Identical texts, such as a
+/// crowd hearing the same event, are only predicted once.
+pub fn evaluate_interactions_json(requests_json: &str) -> Result<String, *mut ApiResult> {
+    let requests: Vec<InteractionRequest> = serde_json::from_str(requests_json).map_err(|e| {
+        Box::into_raw(Box::new(ApiResult::error(format!(
+            "Failed to parse interactions: {}",
+            e
+        ))))
+    })?;
+
+    let sessions = get_npc_sessions()?;
+    let evaluators = requests
+        .iter()
+        .map(|request| {
+            sessions.get(&request.npc_id).ok_or_else(|| {
+                Box::into_raw(Box::new(ApiResult::error(format!(
+                    "NPC session '{}' not found. Call create_npc_session first.",
+                    request.npc_id
+                ))))
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let occurred_ats = evaluators
+        .iter()
+        .map(|evaluator| resolve_occurred_at(evaluator, None))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(e))))?;
+
+    let mut unique_texts: Vec<String> = Vec::new();
+    let mut text_indices: HashMap<&str, usize> = HashMap::new();
+    for request in &requests {
+        text_indices.entry(&request.text).or_insert_with(|| {
+            unique_texts.push(request.text.clone());
+            unique_texts.len() - 1
+        });
+    }
+
+    let predictions =
+        predict_texts_with_cached_model(&unique_texts).map_err(|e| Box::into_raw(Box::new(ApiResult::error(e))))?;
+
+    let mut results = Vec::with_capacity(requests.len());
+    for ((request, evaluator), occurred_at) in requests.iter().zip(evaluators).zip(occurred_ats) {
+        let index = text_indices[request.text.as_str()];
+
+        let json = match evaluator.evaluate_npc_emotion(
+            &request.text,
+            &predictions[index],
+            occurred_at,
+            request.source_id.as_deref(),
+        ) {
+            Ok(final_emotion) => {
+                let mut json = labeled_emotion_json(&final_emotion, &evaluator.label_emotion(&final_emotion));
+                json["npc_id"] = serde_json::json!(request.npc_id);
+                json
+            }
+            Err(e) => serde_json::json!({
+                "npc_id": request.npc_id,
+                "error": format!("Failed to evaluate interaction: {:?}", e)
+            }),
+        };
+        results.push(json);
+    }
+
+    Ok(serde_json::Value::Array(results).to_string())
+}
+
+#[derive(Deserialize)]
+struct RankRequest {
+    texts: Vec<String>,
+    #[serde(default)]
+    target: Option<EmotionPrediction>,
+    #[serde(default)]
+    source_id: Option<String>,
+}
+
+pub fn rank_candidates_json(evaluator: &MemoryEmotionEvaluator, request_json: &str) -> Result<String, String> {
+    let request: RankRequest =
+        serde_json::from_str(request_json).map_err(|e| format!("Failed to parse rank request: {}", e))?;
+
+    let predicted_emotions = predict_texts_with_cached_model(&request.texts)?;
+    let ranked = evaluator
+        .rank_candidates(
+            &predicted_emotions,
+            request.target.as_ref(),
+            request.source_id.as_deref(),
+        )
+        .map_err(|e| format!("Failed to rank candidates: {:?}", e))?;
+
+    let results: Vec<serde_json::Value> = ranked
+        .iter()
+        .map(|candidate| {
+            let emotion = &predicted_emotions[candidate.index];
+            let mut json = labeled_emotion_json(emotion, &evaluator.label_emotion(emotion));
+            json["index"] = serde_json::json!(candidate.index);
+            json["text"] = serde_json::json!(request.texts[candidate.index]);
+            json["distance"] = serde_json::json!(candidate.distance);
+            json
+        })
+        .collect();
+
+    Ok(serde_json::Value::Array(results).to_string())
+}
+
+#[derive(Deserialize)]
+struct EventRequest {
+    #[serde(flatten)]
+    event: GameEvent,
+    #[serde(default)]
+    game_time: Option<GameTime>,
+}
+
+pub fn evaluate_event_json(evaluator: &MemoryEmotionEvaluator, event_json: &str) -> Result<EmotionPrediction, String> {
+    let request: EventRequest =
+        serde_json::from_str(event_json).map_err(|e| format!("Failed to parse event: {}", e))?;
+
+    evaluator
+        .config
+        .events
+        .appraise(&request.event, evaluator.config.personality.dominance.is_some())?;
+    let occurred_at = resolve_occurred_at(evaluator, request.game_time)?;
+
+    evaluator
+        .evaluate_npc_event(&request.event, occurred_at)
+        .map_err(|e| format!("Failed to evaluate event: {:?}", e))
+}
+
+fn resolve_occurred_at(evaluator: &MemoryEmotionEvaluator, game_time: Option<GameTime>) -> Result<GameTime, String> {
+    match game_time {
+        Some(time) => {
+            GameClock::advance_to(&evaluator.npc_id, time).map_err(|e| format!("Failed to update game time: {}", e))?;
+            Ok(time)
+        }
+        None => GameClock::now(&evaluator.npc_id).map_err(|e| format!("Failed to read game time: {}", e)),
+    }
+}
@@ -1,8 +1,19 @@
 use std::os::raw::c_char;
 
+use crate::api::services::trigger_service::check_session_triggers;
 use crate::api::services::validation_service::parse_c_string;
 use crate::api::types::{ApiResult, NpcId};
-use crate::modules::memory::store::{MemoryRecord, MemoryStore};
+use crate::modules::memory::store::{MemoryRecord, MemorySnapshot, MemoryStore};
+use crate::AffectStore;
+use serde::Deserialize;
+
+/// Memory as exported by `get_npc_memory`, or the plain record array of older saves.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MemoryImport {
+    Snapshot(MemorySnapshot),
+    Records(Vec<MemoryRecord>),
+}
 
 pub fn import_memory(npc_id: &NpcId, npc_memory_json: *const c_char) -> Result<(), *mut ApiResult> {
     let memory_str = match parse_c_string(npc_memory_json, "Memory string") {
@@ -14,8 +25,8 @@ pub fn import_memory(npc_id: &NpcId, npc_memory_json: *const c_char) -> Result<(
         return Ok(());
     }
 
-    let memory_records: Vec<MemoryRecord> = match serde_json::from_str(&memory_str) {
-        Ok(records) => records,
+    let memory = match serde_json::from_str(&memory_str) {
+        Ok(memory) => memory,
         Err(e) => {
             return Err(Box::into_raw(Box::new(ApiResult::error(format!(
                 "Failed to parse memory: {}",
@@ -24,8 +35,11 @@ pub fn import_memory(npc_id: &NpcId, npc_memory_json: *const c_char) -> Result<(
         }
     };
 
-    MemoryStore::import(npc_id, memory_records)
-        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to import memory: {}", e)))))
+    match memory {
+        MemoryImport::Snapshot(snapshot) => MemoryStore::restore(npc_id, snapshot),
+        MemoryImport::Records(records) => MemoryStore::import(npc_id, records),
+    }
+    .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to import memory: {}", e)))))
 }
 
 pub fn get_all_memory(npc_id: &NpcId) -> Result<String, *mut ApiResult> {
@@ -48,12 +62,32 @@ pub fn get_all_memory(npc_id: &NpcId) -> Result<String, *mut ApiResult> {
     }
 }
 
-pub fn clear_memory(npc_id: &NpcId) -> Result<String, *mut ApiResult> {
-    match MemoryStore::clear(npc_id) {
-        Ok(_) => Ok("Memory cleared successfully".to_string()),
+pub fn get_memory_snapshot(npc_id: &NpcId) -> Result<String, *mut ApiResult> {
+    let snapshot = match MemoryStore::export(npc_id) {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            return Err(Box::into_raw(Box::new(ApiResult::error(format!(
+                "Failed to get memory records: {}",
+                e
+            )))))
+        }
+    };
+
+    match serde_json::to_string(&snapshot) {
+        Ok(json) => Ok(json),
         Err(e) => Err(Box::into_raw(Box::new(ApiResult::error(format!(
-            "Failed to clear memory: {}",
+            "Failed to serialize memory: {}",
             e
         ))))),
     }
 }
+
+pub fn clear_memory(npc_id: &NpcId) -> Result<String, *mut ApiResult> {
+    MemoryStore::clear(npc_id)
+        .and_then(|_| AffectStore::remove_npc(npc_id))
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to clear memory: {}", e)))))?;
+
+    check_session_triggers(npc_id)?;
+
+    Ok("Memory cleared successfully".to_string())
+}
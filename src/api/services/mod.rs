@@ -1,7 +1,15 @@
+pub mod clock_service;
 pub mod evaluator_service;
 pub mod memory_service;
+pub mod social_service;
+pub mod source_service;
+pub mod trigger_service;
 pub mod validation_service;
 
+pub use clock_service::*;
 pub use evaluator_service::*;
 pub use memory_service::*;
+pub use social_service::*;
+pub use source_service::*;
+pub use trigger_service::*;
 pub use validation_service::*;
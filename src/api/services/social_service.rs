@@ -0,0 +1,83 @@
+use crate::api::services::evaluator_service::{get_npc_sessions, labeled_emotion_json};
+use crate::api::types::{ApiResult, NpcId};
+use crate::{Contagion, ContagionGroup, Gossip};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ContagionRequest {
+    Group(ContagionGroup),
+    Groups(Vec<ContagionGroup>),
+}
+
+pub fn share_gossip(speaker_id: &NpcId, listener_id: &NpcId, source_id: &str) -> Result<String, *mut ApiResult> {
+    let sessions = get_npc_sessions()?;
+
+    let session = |npc_id: &NpcId| {
+        sessions.get(npc_id).ok_or_else(|| {
+            Box::into_raw(Box::new(ApiResult::error(format!(
+                "NPC session '{}' not found. Call create_npc_session first.",
+                npc_id
+            ))))
+        })
+    };
+    let speaker = session(speaker_id)?;
+    let listener = session(listener_id)?;
+
+    let outcome = Gossip::share(speaker, listener, source_id)
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to share gossip: {:?}", e)))))?;
+
+    serde_json::to_string(&outcome)
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to serialize gossip: {}", e)))))
+}
+
+pub fn run_contagion_json(groups_json: &str) -> Result<String, *mut ApiResult> {
+    let groups = match serde_json::from_str(groups_json) {
+        Ok(ContagionRequest::Group(group)) => vec![group],
+        Ok(ContagionRequest::Groups(groups)) => groups,
+        Err(e) => {
+            return Err(Box::into_raw(Box::new(ApiResult::error(format!(
+                "Failed to parse contagion groups: {}",
+                e
+            )))))
+        }
+    };
+
+    let sessions = get_npc_sessions()?;
+
+    let mut resolved = Vec::with_capacity(groups.len());
+    for group in &groups {
+        group
+            .validate()
+            .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Invalid contagion group: {}", e)))))?;
+
+        let evaluators = group
+            .npc_ids
+            .iter()
+            .map(|npc_id| {
+                sessions.get(npc_id).ok_or_else(|| {
+                    Box::into_raw(Box::new(ApiResult::error(format!(
+                        "NPC session '{}' not found. Call create_npc_session first.",
+                        npc_id
+                    ))))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        resolved.push((group, evaluators));
+    }
+
+    let mut results = Vec::new();
+    for (group, evaluators) in resolved {
+        let states = Contagion::step(&evaluators, group)
+            .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to run contagion: {:?}", e)))))?;
+
+        for (evaluator, state) in evaluators.iter().zip(states) {
+            results.push(serde_json::json!({
+                "npc_id": evaluator.npc_id,
+                "emotion": labeled_emotion_json(&state.emotion, &evaluator.label_emotion(&state.emotion))
+            }));
+        }
+    }
+
+    Ok(serde_json::Value::Array(results).to_string())
+}
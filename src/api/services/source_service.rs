@@ -0,0 +1,32 @@
+use crate::api::types::ApiResult;
+use crate::{SourceProfile, SourceRegistry};
+
+pub fn register_source_json(source_json: &str) -> Result<String, *mut ApiResult> {
+    let profile: SourceProfile = serde_json::from_str(source_json)
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to parse source: {}", e)))))?;
+    let source_id = profile.id.clone();
+
+    SourceRegistry::register(profile)
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to register source: {}", e)))))?;
+
+    Ok(format!("Source '{}' registered successfully", source_id))
+}
+
+pub fn get_source_json(source_id: &str) -> Result<String, *mut ApiResult> {
+    let to_error = |e: String| Box::into_raw(Box::new(ApiResult::error(format!("Failed to get source: {}", e))));
+
+    let canonical_id = SourceRegistry::resolve(source_id).map_err(to_error)?;
+    let profile = SourceRegistry::get(&canonical_id)
+        .map_err(to_error)?
+        .unwrap_or_else(|| SourceProfile::new(&canonical_id));
+    let members = SourceRegistry::members(&canonical_id).map_err(to_error)?;
+
+    Ok(serde_json::json!({
+        "id": profile.id,
+        "display_name": profile.display_name.unwrap_or_else(|| profile.id.clone()),
+        "aliases": profile.aliases,
+        "factions": profile.factions,
+        "members": members
+    })
+    .to_string())
+}
@@ -0,0 +1,37 @@
+use crate::api::services::evaluator_service::get_npc_sessions;
+use crate::api::types::{ApiResult, NpcId};
+use crate::{MemoryEmotionEvaluator, Trigger, TriggerStore};
+
+pub fn register_trigger_json(evaluator: &MemoryEmotionEvaluator, trigger_json: &str) -> Result<String, String> {
+    let trigger: Trigger = serde_json::from_str(trigger_json).map_err(|e| format!("Failed to parse trigger: {}", e))?;
+    let trigger_id = trigger.id.clone();
+
+    TriggerStore::register(&evaluator.npc_id, trigger).map_err(|e| format!("Failed to register trigger: {}", e))?;
+    check_npc_triggers(evaluator)?;
+
+    Ok(format!("Trigger '{}' registered successfully", trigger_id))
+}
+
+pub fn check_npc_triggers(evaluator: &MemoryEmotionEvaluator) -> Result<(), String> {
+    evaluator
+        .check_triggers()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to check triggers: {:?}", e))
+}
+
+/// Checks the triggers of `npc_id` if it has a session, e.g. after its memory was changed by id.
+pub fn check_session_triggers(npc_id: &NpcId) -> Result<(), *mut ApiResult> {
+    let sessions = get_npc_sessions()?;
+    match sessions.get(npc_id) {
+        Some(evaluator) => check_npc_triggers(evaluator).map_err(|e| Box::into_raw(Box::new(ApiResult::error(e)))),
+        None => Ok(()),
+    }
+}
+
+pub fn poll_events_json() -> Result<String, *mut ApiResult> {
+    let events = TriggerStore::drain_events()
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to poll events: {}", e)))))?;
+
+    serde_json::to_string(&events)
+        .map_err(|e| Box::into_raw(Box::new(ApiResult::error(format!("Failed to serialize events: {}", e)))))
+}
@@ -2,6 +2,7 @@ use crate::api::types::ApiResult;
 use std::ffi::CStr;
 use std::os::raw::c_char;
 
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn parse_c_string(ptr: *const c_char, field_name: &str) -> Result<String, *mut ApiResult> {
     if ptr.is_null() {
         return Err(Box::into_raw(Box::new(ApiResult::error(format!(
@@ -21,6 +22,7 @@ pub fn parse_c_string(ptr: *const c_char, field_name: &str) -> Result<String, *m
     }
 }
 
+#[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn parse_optional_c_string(ptr: *const c_char) -> Option<String> {
     if ptr.is_null() {
         return None;
@@ -0,0 +1,304 @@
+use crate::PersonalityTraits;
+use serde::{Deserialize, Serialize};
+
+/// OCEAN scores, each between 0.0 and 1.0 with 0.5 as the population average.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BigFiveScores {
+    pub openness: f32,
+    pub conscientiousness: f32,
+    pub extraversion: f32,
+    pub agreeableness: f32,
+    pub neuroticism: f32,
+}
+
+impl BigFiveScores {
+    fn centred(&self) -> [f32; 5] {
+        [
+            self.openness,
+            self.conscientiousness,
+            self.extraversion,
+            self.agreeableness,
+            self.neuroticism,
+        ]
+        .map(|score| score * 2.0 - 1.0)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let scores = [
+            ("openness", self.openness),
+            ("conscientiousness", self.conscientiousness),
+            ("extraversion", self.extraversion),
+            ("agreeableness", self.agreeableness),
+            ("neuroticism", self.neuroticism),
+        ];
+
+        for (name, value) in scores {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!(
+                    "Big Five score '{}' has value {}, but must be between 0.0 and 1.0",
+                    name, value
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// `base + Σ coefficient * (2 * score - 1)`, so an average profile yields `base`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraitMapping {
+    pub base: f32,
+    pub openness: f32,
+    pub conscientiousness: f32,
+    pub extraversion: f32,
+    pub agreeableness: f32,
+    pub neuroticism: f32,
+}
+
+impl TraitMapping {
+    fn new(base: f32, coefficients: [f32; 5]) -> Self {
+        let [openness, conscientiousness, extraversion, agreeableness, neuroticism] = coefficients;
+
+        Self {
+            base,
+            openness,
+            conscientiousness,
+            extraversion,
+            agreeableness,
+            neuroticism,
+        }
+    }
+
+    fn apply(&self, scores: &BigFiveScores, min: f32, max: f32) -> f32 {
+        let coefficients = [
+            self.openness,
+            self.conscientiousness,
+            self.extraversion,
+            self.agreeableness,
+            self.neuroticism,
+        ];
+
+        let value: f32 = self.base
+            + scores
+                .centred()
+                .iter()
+                .zip(coefficients)
+                .map(|(score, coefficient)| score * coefficient)
+                .sum::<f32>();
+
+        value.clamp(min, max)
+    }
+}
+
+impl Default for TraitMapping {
+    fn default() -> Self {
+        Self::new(0.0, [0.0; 5])
+    }
+}
+
+/// Partial [`TraitMapping`] from a config, where missing values keep the default mapping.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TraitMappingOverride {
+    base: Option<f32>,
+    openness: Option<f32>,
+    conscientiousness: Option<f32>,
+    extraversion: Option<f32>,
+    agreeableness: Option<f32>,
+    neuroticism: Option<f32>,
+}
+
+impl TraitMappingOverride {
+    fn over(self, defaults: TraitMapping) -> TraitMapping {
+        TraitMapping {
+            base: self.base.unwrap_or(defaults.base),
+            openness: self.openness.unwrap_or(defaults.openness),
+            conscientiousness: self.conscientiousness.unwrap_or(defaults.conscientiousness),
+            extraversion: self.extraversion.unwrap_or(defaults.extraversion),
+            agreeableness: self.agreeableness.unwrap_or(defaults.agreeableness),
+            neuroticism: self.neuroticism.unwrap_or(defaults.neuroticism),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct BigFiveMappingOverride {
+    valence: TraitMappingOverride,
+    arousal: TraitMappingOverride,
+    reactivity: TraitMappingOverride,
+    inertia: TraitMappingOverride,
+    volatility: TraitMappingOverride,
+    negativity_bias: TraitMappingOverride,
+    susceptibility: TraitMappingOverride,
+}
+
+impl From<BigFiveMappingOverride> for BigFiveMapping {
+    fn from(overrides: BigFiveMappingOverride) -> Self {
+        let defaults = BigFiveMapping::default();
+
+        Self {
+            valence: overrides.valence.over(defaults.valence),
+            arousal: overrides.arousal.over(defaults.arousal),
+            reactivity: overrides.reactivity.over(defaults.reactivity),
+            inertia: overrides.inertia.over(defaults.inertia),
+            volatility: overrides.volatility.over(defaults.volatility),
+            negativity_bias: overrides.negativity_bias.over(defaults.negativity_bias),
+            susceptibility: overrides.susceptibility.over(defaults.susceptibility),
+        }
+    }
+}
+
+/// How Big Five scores turn into affect parameters. The defaults follow the usual findings that
+/// extraversion and agreeableness raise positive affect, neuroticism raises negative affect and
+/// reactivity, and conscientiousness steadies the character. Any value can be overridden, and the
+/// values a config leaves out keep their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BigFiveMappingOverride")]
+pub struct BigFiveMapping {
+    pub valence: TraitMapping,
+    pub arousal: TraitMapping,
+    pub reactivity: TraitMapping,
+    pub inertia: TraitMapping,
+    pub volatility: TraitMapping,
+    pub negativity_bias: TraitMapping,
+    pub susceptibility: TraitMapping,
+}
+
+impl Default for BigFiveMapping {
+    fn default() -> Self {
+        Self {
+            // Coefficients are ordered openness, conscientiousness, extraversion, agreeableness, neuroticism.
+            valence: TraitMapping::new(0.0, [0.0, 0.1, 0.3, 0.3, -0.4]),
+            arousal: TraitMapping::new(0.0, [0.2, -0.1, 0.4, 0.0, 0.3]),
+            reactivity: TraitMapping::new(1.0, [0.0, -0.2, 0.3, 0.0, 0.5]),
+            inertia: TraitMapping::new(0.0, [-0.1, 0.4, 0.0, 0.0, -0.2]),
+            volatility: TraitMapping::new(0.0, [0.0, -0.2, 0.0, 0.0, 0.6]),
+            negativity_bias: TraitMapping::new(0.0, [0.0, 0.0, -0.1, -0.2, 0.6]),
+            susceptibility: TraitMapping::new(0.5, [0.0, -0.1, 0.2, 0.2, 0.2]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BigFiveConfig {
+    #[serde(flatten)]
+    pub scores: BigFiveScores,
+    #[serde(default)]
+    pub mapping: BigFiveMapping,
+}
+
+impl BigFiveConfig {
+    /// Personality derived from the scores. `dominance` is not derived and is kept from `personality`.
+    pub fn derive_personality(&self, personality: &PersonalityTraits) -> PersonalityTraits {
+        let scores = &self.scores;
+        let mapping = &self.mapping;
+
+        PersonalityTraits {
+            valence: mapping.valence.apply(scores, -1.0, 1.0),
+            arousal: mapping.arousal.apply(scores, -1.0, 1.0),
+            dominance: personality.dominance,
+            reactivity: mapping.reactivity.apply(scores, 0.0, 2.0),
+            inertia: mapping.inertia.apply(scores, 0.0, 1.0),
+            volatility: mapping.volatility.apply(scores, 0.0, 1.0),
+            negativity_bias: mapping.negativity_bias.apply(scores, 0.0, 1.0),
+            susceptibility: mapping.susceptibility.apply(scores, 0.0, 1.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigFiveConfig, BigFiveMapping, BigFiveScores, TraitMapping};
+    use crate::PersonalityTraits;
+
+    fn scores(
+        openness: f32,
+        conscientiousness: f32,
+        extraversion: f32,
+        agreeableness: f32,
+        neuroticism: f32,
+    ) -> BigFiveScores {
+        BigFiveScores {
+            openness,
+            conscientiousness,
+            extraversion,
+            agreeableness,
+            neuroticism,
+        }
+    }
+
+    #[test]
+    fn test_average_profile_maps_to_defaults() {
+        let config = BigFiveConfig {
+            scores: scores(0.5, 0.5, 0.5, 0.5, 0.5),
+            mapping: BigFiveMapping::default(),
+        };
+
+        let personality = config.derive_personality(&PersonalityTraits::new());
+        let defaults = PersonalityTraits::new();
+
+        assert_eq!(personality.valence, defaults.valence);
+        assert_eq!(personality.arousal, defaults.arousal);
+        assert_eq!(personality.reactivity, defaults.reactivity);
+        assert_eq!(personality.inertia, defaults.inertia);
+        assert_eq!(personality.susceptibility, defaults.susceptibility);
+    }
+
+    #[test]
+    fn test_neurotic_profile() {
+        let config = BigFiveConfig {
+            scores: scores(0.5, 0.2, 0.3, 0.3, 0.95),
+            mapping: BigFiveMapping::default(),
+        };
+
+        let personality = config.derive_personality(&PersonalityTraits::new());
+
+        assert!(personality.valence < 0.0);
+        assert!(personality.reactivity > 1.0);
+        assert!(personality.volatility > 0.0);
+        assert!(personality.negativity_bias > 0.0);
+        assert!(personality.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mapping_override_and_dominance_kept() {
+        let mapping = BigFiveMapping {
+            valence: TraitMapping {
+                base: 0.5,
+                ..TraitMapping::default()
+            },
+            ..BigFiveMapping::default()
+        };
+        let config = BigFiveConfig {
+            scores: scores(1.0, 1.0, 1.0, 1.0, 1.0),
+            mapping,
+        };
+
+        let base = PersonalityTraits {
+            dominance: Some(0.4),
+            ..PersonalityTraits::new()
+        };
+        let personality = config.derive_personality(&base);
+
+        assert_eq!(personality.valence, 0.5);
+        assert_eq!(personality.dominance, Some(0.4));
+    }
+
+    #[test]
+    fn test_partial_mapping_keeps_default_coefficients() {
+        let mapping: BigFiveMapping = serde_json::from_str(r#"{ "valence": { "base": 0.1 } }"#).unwrap();
+        let defaults = BigFiveMapping::default();
+
+        assert_eq!(mapping.valence.base, 0.1);
+        assert_eq!(mapping.valence.neuroticism, defaults.valence.neuroticism);
+        assert_eq!(mapping.arousal, defaults.arousal);
+    }
+
+    #[test]
+    fn test_big_five_scores_validate() {
+        assert!(scores(0.0, 1.0, 0.5, 0.5, 0.5).validate().is_ok());
+        assert!(scores(1.2, 0.5, 0.5, 0.5, 0.5).validate().is_err());
+    }
+}
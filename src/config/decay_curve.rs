@@ -0,0 +1,139 @@
+use crate::GameTime;
+use serde::{Deserialize, Serialize};
+
+/// Weight given to a memory depending on its age in game minutes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DecayCurve {
+    /// `e^(-rate * age)`
+    Exponential { rate: f32 },
+    /// Weight halves every `half_life` minutes.
+    HalfLife { half_life: f32 },
+    /// Ebbinghaus-style forgetting: `(1 + age / scale)^(-exponent)`.
+    PowerLaw { scale: f32, exponent: f32 },
+    /// Falls linearly from 1 to 0 over `lifetime` minutes.
+    Linear { lifetime: f32 },
+    /// Full weight for `duration` minutes, forgotten afterwards.
+    Step { duration: f32 },
+}
+
+impl DecayCurve {
+    pub fn weight(&self, age: GameTime) -> f32 {
+        let age = age.max(0) as f32;
+
+        match *self {
+            DecayCurve::Exponential { rate } => (-rate * age).exp(),
+            DecayCurve::HalfLife { half_life } => 0.5_f32.powf(age / half_life),
+            DecayCurve::PowerLaw { scale, exponent } => (1.0 + age / scale).powf(-exponent),
+            DecayCurve::Linear { lifetime } => (1.0 - age / lifetime).max(0.0),
+            DecayCurve::Step { duration } => {
+                if age <= duration {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let parameters = match self {
+            DecayCurve::Exponential { rate } => vec![("rate", *rate, true)],
+            DecayCurve::HalfLife { half_life } => vec![("half_life", *half_life, false)],
+            DecayCurve::PowerLaw { scale, exponent } => vec![("scale", *scale, false), ("exponent", *exponent, true)],
+            DecayCurve::Linear { lifetime } => vec![("lifetime", *lifetime, false)],
+            DecayCurve::Step { duration } => vec![("duration", *duration, true)],
+        };
+
+        for (name, value, allow_zero) in parameters {
+            if !value.is_finite() || value < 0.0 || (!allow_zero && value == 0.0) {
+                return Err(format!(
+                    "Decay curve parameter '{}' has value {}, but must be {}",
+                    name,
+                    value,
+                    if allow_zero { "zero or positive" } else { "positive" }
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecayCurve;
+
+    #[test]
+    fn test_exponential_curve() {
+        let curve = DecayCurve::Exponential { rate: 0.1 };
+        assert_eq!(curve.weight(0), 1.0);
+        assert!((curve.weight(10) - (-1.0_f32).exp()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_half_life_curve() {
+        let curve = DecayCurve::HalfLife { half_life: 60.0 };
+        assert_eq!(curve.weight(0), 1.0);
+        assert!((curve.weight(60) - 0.5).abs() < 1e-6);
+        assert!((curve.weight(120) - 0.25).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_power_law_curve() {
+        let curve = DecayCurve::PowerLaw {
+            scale: 10.0,
+            exponent: 1.0,
+        };
+        assert_eq!(curve.weight(0), 1.0);
+        assert!((curve.weight(10) - 0.5).abs() < 1e-6);
+        assert!(curve.weight(1000) > 0.0);
+    }
+
+    #[test]
+    fn test_linear_curve() {
+        let curve = DecayCurve::Linear { lifetime: 100.0 };
+        assert_eq!(curve.weight(0), 1.0);
+        assert!((curve.weight(25) - 0.75).abs() < 1e-6);
+        assert_eq!(curve.weight(100), 0.0);
+        assert_eq!(curve.weight(500), 0.0);
+    }
+
+    #[test]
+    fn test_step_curve() {
+        let curve = DecayCurve::Step { duration: 1440.0 };
+        assert_eq!(curve.weight(0), 1.0);
+        assert_eq!(curve.weight(1440), 1.0);
+        assert_eq!(curve.weight(1441), 0.0);
+    }
+
+    #[test]
+    fn test_negative_age_counts_as_new() {
+        let curve = DecayCurve::Linear { lifetime: 10.0 };
+        assert_eq!(curve.weight(-5), 1.0);
+    }
+
+    #[test]
+    fn test_decay_curve_validate() {
+        assert!(DecayCurve::Exponential { rate: 0.0 }.validate().is_ok());
+        assert!(DecayCurve::HalfLife { half_life: 0.0 }.validate().is_err());
+        assert!(DecayCurve::Linear { lifetime: -1.0 }.validate().is_err());
+        assert!(DecayCurve::Step { duration: f32::NAN }.validate().is_err());
+    }
+
+    #[test]
+    fn test_decay_curve_deserialize() {
+        let curve: DecayCurve = serde_json::from_str(r#"{"type": "half_life", "half_life": 30.0}"#).unwrap();
+        assert_eq!(curve, DecayCurve::HalfLife { half_life: 30.0 });
+
+        let curve: DecayCurve =
+            serde_json::from_str(r#"{"type": "power_law", "scale": 5.0, "exponent": 0.5}"#).unwrap();
+        assert_eq!(
+            curve,
+            DecayCurve::PowerLaw {
+                scale: 5.0,
+                exponent: 0.5
+            }
+        );
+    }
+}
@@ -1,3 +1,10 @@
+pub mod big_five;
+pub mod decay_curve;
 pub mod npc_config;
 
-pub use npc_config::{Identity, MemoryConfig, NpcConfig, PersonalityTraits};
+pub use big_five::{BigFiveConfig, BigFiveMapping, BigFiveScores, TraitMapping};
+pub use decay_curve::DecayCurve;
+pub use npc_config::{
+    BlendWeights, BlendingConfig, DominanceConfig, FactionConfig, GossipConfig, HomeostasisConfig, Identity,
+    MemoryConfig, MoodConfig, NpcConfig, PersonalityTraits, RelationshipConfig, TextBlendWeights,
+};
@@ -1,3 +1,6 @@
+use crate::config::big_five::BigFiveConfig;
+use crate::config::decay_curve::DecayCurve;
+use crate::{EventConfig, LabelingConfig, ObservationConfig};
 use serde::{Deserialize, Serialize};
 use std::convert::Into;
 
@@ -28,6 +31,24 @@ impl Default for Identity {
 pub struct PersonalityTraits {
     pub valence: PersonalityValue,
     pub arousal: PersonalityValue,
+    /// Baseline dominance. Dominance is only tracked for NPCs that define it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominance: Option<PersonalityValue>,
+    /// Multiplier on the weight of the text emotion, from 0.0 (unmoved by words) to 2.0.
+    #[serde(default = "PersonalityTraits::default_reactivity")]
+    pub reactivity: PersonalityValue,
+    /// Share of the previous mood kept through each interaction, from 0.0 to 1.0.
+    #[serde(default)]
+    pub inertia: PersonalityValue,
+    /// Amplification of remembered deviations from the baseline, from 0.0 to 1.0.
+    #[serde(default)]
+    pub volatility: PersonalityValue,
+    /// Extra weight given to unpleasant text and memories, from 0.0 to 1.0.
+    #[serde(default)]
+    pub negativity_bias: PersonalityValue,
+    /// How strongly the emotions of surrounding NPCs rub off during contagion, from 0.0 to 1.0.
+    #[serde(default = "PersonalityTraits::default_susceptibility")]
+    pub susceptibility: PersonalityValue,
 }
 
 impl PersonalityTraits {
@@ -35,11 +56,28 @@ impl PersonalityTraits {
         Self {
             valence: 0.0,
             arousal: 0.0,
+            dominance: None,
+            reactivity: Self::default_reactivity(),
+            inertia: 0.0,
+            volatility: 0.0,
+            negativity_bias: 0.0,
+            susceptibility: Self::default_susceptibility(),
         }
     }
 
+    fn default_reactivity() -> PersonalityValue {
+        1.0
+    }
+
+    fn default_susceptibility() -> PersonalityValue {
+        0.5
+    }
+
     pub fn validate(&self) -> Result<(), String> {
-        let traits = [("valence", self.valence), ("arousal", self.arousal)];
+        let mut traits = vec![("valence", self.valence), ("arousal", self.arousal)];
+        if let Some(dominance) = self.dominance {
+            traits.push(("dominance", dominance));
+        }
 
         for (name, value) in traits {
             if value < -1.0 || value > 1.0 {
@@ -50,6 +88,23 @@ impl PersonalityTraits {
             }
         }
 
+        let modifiers = [
+            ("reactivity", self.reactivity, 2.0),
+            ("inertia", self.inertia, 1.0),
+            ("volatility", self.volatility, 1.0),
+            ("negativity_bias", self.negativity_bias, 1.0),
+            ("susceptibility", self.susceptibility, 1.0),
+        ];
+
+        for (name, value, max) in modifiers {
+            if !(0.0..=max).contains(&value) {
+                return Err(format!(
+                    "Personality trait '{}' has value {}, but must be between 0.0 and {:.1}",
+                    name, value, max
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -61,29 +116,360 @@ impl Default for PersonalityTraits {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MemoryConfig {
     pub decay_rate: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decay_curve: Option<DecayCurve>,
 }
 
 impl MemoryConfig {
     pub fn new(decay_rate: impl Into<f32>) -> Self {
         Self {
             decay_rate: decay_rate.into(),
+            decay_curve: None,
+        }
+    }
+
+    pub fn with_curve(decay_curve: DecayCurve) -> Self {
+        Self {
+            decay_curve: Some(decay_curve),
+            ..Self::default()
         }
     }
+
+    /// The configured curve, or an exponential curve driven by `decay_rate` when none is set.
+    pub fn curve(&self) -> DecayCurve {
+        self.decay_curve
+            .clone()
+            .unwrap_or(DecayCurve::Exponential { rate: self.decay_rate })
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        self.curve().validate()
+    }
 }
 
 impl Default for MemoryConfig {
     fn default() -> Self {
-        Self { decay_rate: 0.1 }
+        Self {
+            decay_rate: 0.1,
+            decay_curve: None,
+        }
+    }
+}
+
+/// Time constants, in game minutes, of the emotion and mood dynamics.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MoodConfig {
+    /// How fast the emotion settles back onto the mood.
+    pub emotion_time_constant: f32,
+    /// How fast the mood drifts towards the emotion.
+    pub mood_time_constant: f32,
+    /// How fast the mood returns to the personality baseline.
+    pub baseline_time_constant: f32,
+    /// Fraction of the gap between mood and emotion closed immediately by each interaction.
+    pub interaction_impulse: f32,
+}
+
+impl MoodConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        let time_constants = [
+            ("emotion_time_constant", self.emotion_time_constant),
+            ("mood_time_constant", self.mood_time_constant),
+            ("baseline_time_constant", self.baseline_time_constant),
+        ];
+
+        for (name, value) in time_constants {
+            if !value.is_finite() || value <= 0.0 {
+                return Err(format!("Mood '{}' has value {}, but must be positive", name, value));
+            }
+        }
+
+        if !(0.0..=1.0).contains(&self.interaction_impulse) {
+            return Err(format!(
+                "Mood 'interaction_impulse' has value {}, but must be between 0.0 and 1.0",
+                self.interaction_impulse
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for MoodConfig {
+    fn default() -> Self {
+        Self {
+            emotion_time_constant: 30.0,
+            mood_time_constant: 240.0,
+            baseline_time_constant: 1440.0,
+            interaction_impulse: 0.1,
+        }
+    }
+}
+
+/// Return of the memory-based emotions, overall and towards each source, to the personality baseline
+/// while nothing happens. The mood of the affect state has its own `MoodConfig::baseline_time_constant`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HomeostasisConfig {
+    /// Relaxation rate per game minute. Zero disables homeostasis.
+    pub rate: f32,
+    /// Distance to the baseline, on every axis, under which the NPC counts as back to baseline.
+    pub tolerance: f32,
+}
+
+impl HomeostasisConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.rate.is_finite() || self.rate < 0.0 {
+            return Err(format!(
+                "Homeostasis 'rate' has value {}, but must be zero or positive",
+                self.rate
+            ));
+        }
+
+        if !self.tolerance.is_finite() || self.tolerance <= 0.0 || self.tolerance > 1.0 {
+            return Err(format!(
+                "Homeostasis 'tolerance' has value {}, but must be greater than 0.0 and at most 1.0",
+                self.tolerance
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for HomeostasisConfig {
+    fn default() -> Self {
+        Self {
+            rate: 0.002,
+            tolerance: 0.05,
+        }
+    }
+}
+
+/// How feelings about faction members carry over to the faction itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FactionConfig {
+    /// Weight of memories about members relative to memories about the faction itself.
+    pub spillover: f32,
+}
+
+impl FactionConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.spillover) {
+            return Err(format!(
+                "Faction 'spillover' has value {}, but must be between 0.0 and 1.0",
+                self.spillover
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for FactionConfig {
+    fn default() -> Self {
+        Self { spillover: 0.5 }
+    }
+}
+
+/// How memories are shared with and received from other NPCs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GossipConfig {
+    /// How much of a trusted speaker's feelings the NPC adopts when hearing gossip.
+    pub credulity: f32,
+    /// Most recent memories about a source the NPC passes on in one conversation.
+    pub max_shared: usize,
+}
+
+impl GossipConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.credulity) {
+            return Err(format!(
+                "Gossip 'credulity' has value {}, but must be between 0.0 and 1.0",
+                self.credulity
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GossipConfig {
+    fn default() -> Self {
+        Self {
+            credulity: 0.5,
+            max_shared: 5,
+        }
+    }
+}
+
+/// Shape of the relationship metrics computed from memories about a source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RelationshipConfig {
+    /// Decay-weighted number of interactions at which familiarity reaches about 63%.
+    pub familiarity_saturation: f32,
+    /// Weight of the neutral 0.5 starting trust against actual interactions.
+    pub trust_prior: f32,
+}
+
+impl RelationshipConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.familiarity_saturation.is_finite() || self.familiarity_saturation <= 0.0 {
+            return Err(format!(
+                "Relationship 'familiarity_saturation' has value {}, but must be positive",
+                self.familiarity_saturation
+            ));
+        }
+
+        if !self.trust_prior.is_finite() || self.trust_prior <= 0.0 {
+            return Err(format!(
+                "Relationship 'trust_prior' has value {}, but must be positive",
+                self.trust_prior
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for RelationshipConfig {
+    fn default() -> Self {
+        Self {
+            familiarity_saturation: 5.0,
+            trust_prior: 1.0,
+        }
+    }
+}
+
+/// Dominance used for the text when the model has no dominance output.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DominanceConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<PersonalityValue>,
+}
+
+impl DominanceConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        match self.fallback {
+            Some(fallback) if !(-1.0..=1.0).contains(&fallback) => Err(format!(
+                "Dominance fallback has value {}, but must be between -1.0 and 1.0",
+                fallback
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlendWeights {
+    pub source: f32,
+    pub text: f32,
+    pub global: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextBlendWeights {
+    pub text: f32,
+    pub global: f32,
+}
+
+/// Weights used to combine the emotion towards the source, the emotion of the text and the global emotion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlendingConfig {
+    pub with_source: BlendWeights,
+    pub without_source: TextBlendWeights,
+}
+
+impl BlendingConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        Self::validate_convex(
+            "with_source",
+            &[
+                ("source", self.with_source.source),
+                ("text", self.with_source.text),
+                ("global", self.with_source.global),
+            ],
+        )?;
+        Self::validate_convex(
+            "without_source",
+            &[
+                ("text", self.without_source.text),
+                ("global", self.without_source.global),
+            ],
+        )
+    }
+
+    fn validate_convex(section: &str, weights: &[(&str, f32)]) -> Result<(), String> {
+        for (name, value) in weights {
+            if !(0.0..=1.0).contains(value) {
+                return Err(format!(
+                    "Blending weight '{}.{}' has value {}, but must be between 0.0 and 1.0",
+                    section, name, value
+                ));
+            }
+        }
+
+        let total: f32 = weights.iter().map(|(_, value)| value).sum();
+        if (total - 1.0).abs() > 1e-3 {
+            return Err(format!(
+                "Blending weights in '{}' sum to {}, but must sum to 1.0",
+                section, total
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for BlendingConfig {
+    fn default() -> Self {
+        Self {
+            with_source: BlendWeights {
+                source: 0.5,
+                text: 0.35,
+                global: 0.15,
+            },
+            without_source: TextBlendWeights { text: 0.7, global: 0.3 },
+        }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NpcConfig {
     pub identity: Identity,
+    #[serde(default)]
     pub personality: PersonalityTraits,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub big_five: Option<BigFiveConfig>,
     pub memory: MemoryConfig,
+    #[serde(default)]
+    pub blending: BlendingConfig,
+    #[serde(default)]
+    pub dominance: DominanceConfig,
+    #[serde(default)]
+    pub labels: LabelingConfig,
+    #[serde(default)]
+    pub events: EventConfig,
+    #[serde(default)]
+    pub observation: ObservationConfig,
+    #[serde(default)]
+    pub factions: FactionConfig,
+    #[serde(default)]
+    pub gossip: GossipConfig,
+    #[serde(default)]
+    pub relationship: RelationshipConfig,
+    #[serde(default)]
+    pub mood: MoodConfig,
+    #[serde(default)]
+    pub homeostasis: HomeostasisConfig,
 }
 
 impl Default for NpcConfig {
@@ -91,14 +477,69 @@ impl Default for NpcConfig {
         Self {
             identity: Identity::default(),
             personality: PersonalityTraits::default(),
+            big_five: None,
             memory: MemoryConfig::default(),
+            blending: BlendingConfig::default(),
+            dominance: DominanceConfig::default(),
+            labels: LabelingConfig::default(),
+            events: EventConfig::default(),
+            observation: ObservationConfig::default(),
+            factions: FactionConfig::default(),
+            gossip: GossipConfig::default(),
+            relationship: RelationshipConfig::default(),
+            mood: MoodConfig::default(),
+            homeostasis: HomeostasisConfig::default(),
+        }
+    }
+}
+
+impl NpcConfig {
+    /// Config with `personality` derived from `big_five` when a Big Five profile is given. The derived
+    /// traits replace any explicit `personality` values, only `dominance` is kept.
+    pub fn resolved(&self) -> NpcConfig {
+        let mut config = self.clone();
+
+        if let Some(big_five) = &self.big_five {
+            config.personality = big_five.derive_personality(&self.personality);
         }
+
+        config
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(big_five) = &self.big_five {
+            big_five.scores.validate()?;
+        }
+        self.personality.validate()?;
+        self.memory.validate()?;
+        self.blending.validate()?;
+        self.dominance.validate()?;
+        if self.dominance.fallback.is_some() && self.personality.dominance.is_none() {
+            return Err(
+                "Dominance fallback requires personality.dominance, which enables the dominance axis".to_string(),
+            );
+        }
+        self.labels.validate()?;
+        self.events.validate()?;
+        self.observation.validate()?;
+        self.factions.validate()?;
+        self.gossip.validate()?;
+        self.relationship.validate()?;
+        self.mood.validate()?;
+        self.homeostasis.validate()?;
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Identity, MemoryConfig, NpcConfig, PersonalityTraits};
+    use super::{
+        BlendWeights, BlendingConfig, DominanceConfig, FactionConfig, GossipConfig, HomeostasisConfig, Identity,
+        MemoryConfig, MoodConfig, NpcConfig, PersonalityTraits, RelationshipConfig,
+    };
+    use crate::config::DecayCurve;
+    use crate::{EventConfig, LabelingConfig, ObservationConfig};
 
     #[test]
     fn test_npc_config_default() {
@@ -131,8 +572,32 @@ mod tests {
         traits.arousal = -0.3;
         assert!(traits.validate().is_ok());
 
+        traits.dominance = Some(-0.6);
+        assert!(traits.validate().is_ok());
+
+        traits.dominance = Some(-1.6);
+        assert!(traits.validate().is_err());
+
+        traits.dominance = None;
         traits.valence = 1.5;
         assert!(traits.validate().is_err());
+
+        traits.valence = 0.5;
+        traits.reactivity = 1.8;
+        traits.inertia = 0.6;
+        traits.negativity_bias = 0.4;
+        assert!(traits.validate().is_ok());
+
+        traits.reactivity = 2.5;
+        assert!(traits.validate().is_err());
+
+        traits.reactivity = 1.0;
+        traits.volatility = -0.1;
+        assert!(traits.validate().is_err());
+
+        traits.volatility = 0.0;
+        traits.susceptibility = 1.2;
+        assert!(traits.validate().is_err());
     }
 
     #[test]
@@ -156,11 +621,191 @@ mod tests {
             personality: PersonalityTraits {
                 valence: 0.3,
                 arousal: -0.2,
+                ..PersonalityTraits::new()
             },
+            big_five: None,
             memory: MemoryConfig::new(0.15),
+            blending: BlendingConfig::default(),
+            dominance: DominanceConfig::default(),
+            labels: LabelingConfig::default(),
+            events: EventConfig::default(),
+            observation: ObservationConfig::default(),
+            factions: FactionConfig::default(),
+            gossip: GossipConfig::default(),
+            relationship: RelationshipConfig::default(),
+            mood: MoodConfig::default(),
+            homeostasis: HomeostasisConfig::default(),
         };
         assert_eq!(custom.identity.name, "Test");
         assert_eq!(custom.personality.valence, 0.3);
         assert_eq!(custom.memory.decay_rate, 0.15);
     }
+
+    #[test]
+    fn test_memory_config_curve() {
+        let memory = MemoryConfig::new(0.2);
+        assert_eq!(memory.curve(), DecayCurve::Exponential { rate: 0.2 });
+
+        let memory = MemoryConfig::with_curve(DecayCurve::Step { duration: 4320.0 });
+        assert_eq!(memory.curve(), DecayCurve::Step { duration: 4320.0 });
+        assert!(memory.validate().is_ok());
+
+        let memory = MemoryConfig::with_curve(DecayCurve::HalfLife { half_life: -1.0 });
+        assert!(memory.validate().is_err());
+    }
+
+    #[test]
+    fn test_npc_config_parses_decay_curve() {
+        let config: NpcConfig = serde_json::from_str(
+            r#"{
+                "identity": {"name": "Smith", "background": "Blacksmith"},
+                "personality": {"valence": 0.0, "arousal": 0.0},
+                "memory": {"decay_curve": {"type": "linear", "lifetime": 10080.0}}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.memory.decay_rate, 0.1);
+        assert_eq!(config.memory.curve(), DecayCurve::Linear { lifetime: 10080.0 });
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_blending_config_validate() {
+        let mut blending = BlendingConfig::default();
+        assert!(blending.validate().is_ok());
+
+        blending.with_source = BlendWeights {
+            source: 0.8,
+            text: 0.15,
+            global: 0.05,
+        };
+        assert!(blending.validate().is_ok());
+
+        blending.with_source.text = 0.5;
+        assert!(blending.validate().is_err());
+
+        blending.with_source = BlendWeights {
+            source: 1.2,
+            text: -0.1,
+            global: -0.1,
+        };
+        assert!(blending.validate().is_err());
+
+        blending = BlendingConfig::default();
+        blending.without_source.text = 0.9;
+        assert!(blending.validate().is_err());
+    }
+
+    #[test]
+    fn test_npc_config_parses_blending() {
+        let config: NpcConfig = serde_json::from_str(
+            r#"{
+                "identity": {"name": "Bandit", "background": "Impulsive"},
+                "personality": {"valence": -0.2, "arousal": 0.4},
+                "memory": {"decay_rate": 0.1},
+                "blending": {"with_source": {"source": 0.2, "text": 0.7, "global": 0.1}}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.blending.with_source.text, 0.7);
+        assert_eq!(config.blending.without_source.text, 0.7);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_mood_config_validate() {
+        assert!(MoodConfig::default().validate().is_ok());
+
+        let mood = MoodConfig {
+            mood_time_constant: 0.0,
+            ..MoodConfig::default()
+        };
+        assert!(mood.validate().is_err());
+
+        let mood = MoodConfig {
+            interaction_impulse: 1.5,
+            ..MoodConfig::default()
+        };
+        assert!(mood.validate().is_err());
+    }
+
+    #[test]
+    fn test_dominance_fallback_requires_personality_dominance() {
+        let fallback_only = NpcConfig {
+            dominance: DominanceConfig { fallback: Some(0.3) },
+            ..NpcConfig::default()
+        };
+        assert!(fallback_only.validate().is_err());
+
+        let with_dominance = NpcConfig {
+            personality: PersonalityTraits {
+                dominance: Some(0.1),
+                ..PersonalityTraits::default()
+            },
+            ..fallback_only
+        };
+        assert!(with_dominance.validate().is_ok());
+    }
+
+    #[test]
+    fn test_homeostasis_config_validate() {
+        assert!(HomeostasisConfig::default().validate().is_ok());
+
+        let homeostasis = HomeostasisConfig {
+            rate: 0.0,
+            ..HomeostasisConfig::default()
+        };
+        assert!(homeostasis.validate().is_ok());
+
+        let homeostasis = HomeostasisConfig {
+            rate: -0.1,
+            ..HomeostasisConfig::default()
+        };
+        assert!(homeostasis.validate().is_err());
+
+        let homeostasis = HomeostasisConfig {
+            tolerance: 0.0,
+            ..HomeostasisConfig::default()
+        };
+        assert!(homeostasis.validate().is_err());
+
+        let homeostasis = HomeostasisConfig {
+            tolerance: f32::NAN,
+            ..HomeostasisConfig::default()
+        };
+        assert!(homeostasis.validate().is_err());
+
+        let homeostasis = HomeostasisConfig {
+            rate: f32::NAN,
+            ..HomeostasisConfig::default()
+        };
+        assert!(homeostasis.validate().is_err());
+    }
+
+    #[test]
+    fn test_npc_config_resolves_big_five() {
+        let config: NpcConfig = serde_json::from_str(
+            r#"{
+                "identity": {"name": "Blacksmith", "background": "Grumpy"},
+                "big_five": {
+                    "openness": 0.3,
+                    "conscientiousness": 0.9,
+                    "extraversion": 0.2,
+                    "agreeableness": 0.1,
+                    "neuroticism": 0.7
+                },
+                "memory": {"decay_rate": 0.1}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(config.personality.valence, 0.0);
+
+        let resolved = config.resolved();
+        assert!(resolved.personality.valence < 0.0);
+        assert!(resolved.personality.inertia > 0.0);
+        assert!(resolved.validate().is_ok());
+    }
 }
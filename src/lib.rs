@@ -2,6 +2,18 @@ pub mod api;
 pub mod config;
 pub mod modules;
 
-pub use config::{Identity, MemoryConfig, NpcConfig, PersonalityTraits};
-pub use modules::emotion::{EmotionPrediction, EmotionPredictor, EmotionPredictorError};
-pub use modules::memory::{MemoryEmotionEvaluator, MemoryRecord, MemoryStore};
+pub use config::{BigFiveConfig, BlendingConfig, DecayCurve, Identity, MemoryConfig, NpcConfig, PersonalityTraits};
+pub use modules::affect::{AffectState, AffectStore, Homeostasis};
+pub use modules::appraisal::{EventAppraisal, EventConfig, GameEvent, ObservationConfig};
+pub use modules::clock::{GameClock, GameTime};
+pub use modules::emotion::{
+    EmotionLabel, EmotionPrediction, EmotionPredictor, EmotionPredictorError, EmotionRegion, LabelingConfig,
+    ManifestFile, ModelDownloader, ModelManifest, PredictorOptions, RankedCandidate, TensorSpec,
+};
+pub use modules::memory::{
+    AppliedBlendWeights, EvaluationBreakdown, MemoryContribution, MemoryEmotionEvaluator, MemoryRecord, MemorySnapshot,
+    MemoryStore,
+};
+pub use modules::social::{Contagion, ContagionGroup, Gossip, GossipOutcome, Relationship};
+pub use modules::source::{SourceProfile, SourceRegistry};
+pub use modules::trigger::{EmotionEvent, EmotionEventKind, Trigger, TriggerAxis, TriggerCondition, TriggerStore};
@@ -0,0 +1,99 @@
+use crate::config::HomeostasisConfig;
+use crate::{EmotionPrediction, GameTime, PersonalityTraits};
+
+pub struct Homeostasis;
+
+impl Homeostasis {
+    /// Pulls `emotion` towards the personality baseline after `idle` minutes without new memories.
+    pub fn relax(
+        emotion: &EmotionPrediction,
+        personality: &PersonalityTraits,
+        config: &HomeostasisConfig,
+        idle: GameTime,
+    ) -> EmotionPrediction {
+        let retention = (-config.rate * idle.max(0) as f32).exp();
+        let relax = |value: f32, baseline: f32| baseline + (value - baseline) * retention;
+
+        EmotionPrediction::new(
+            relax(emotion.valence, personality.valence),
+            relax(emotion.arousal, personality.arousal),
+        )
+        .with_dominance(match (emotion.dominance, personality.dominance) {
+            (Some(dominance), Some(baseline)) => Some(relax(dominance, baseline)),
+            (dominance, _) => dominance,
+        })
+    }
+
+    /// Minutes until every axis is within `tolerance` of the baseline, or `None` when the NPC never calms down.
+    pub fn time_to_baseline(
+        emotion: &EmotionPrediction,
+        personality: &PersonalityTraits,
+        config: &HomeostasisConfig,
+    ) -> Option<GameTime> {
+        let mut deviation = (emotion.valence - personality.valence)
+            .abs()
+            .max((emotion.arousal - personality.arousal).abs());
+        if let (Some(dominance), Some(baseline)) = (emotion.dominance, personality.dominance) {
+            deviation = deviation.max((dominance - baseline).abs());
+        }
+
+        if deviation <= config.tolerance {
+            return Some(0);
+        }
+
+        if config.rate <= 0.0 {
+            return None;
+        }
+
+        Some(((deviation / config.tolerance).ln() / config.rate).ceil() as GameTime)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Homeostasis;
+    use crate::config::HomeostasisConfig;
+    use crate::{EmotionPrediction, PersonalityTraits};
+
+    #[test]
+    fn test_relax_moves_towards_baseline() {
+        let personality = PersonalityTraits::new();
+        let config = HomeostasisConfig {
+            rate: 0.01,
+            tolerance: 0.05,
+        };
+        let angry = EmotionPrediction::new(-0.8, 0.6);
+
+        let unchanged = Homeostasis::relax(&angry, &personality, &config, 0);
+        assert_eq!(unchanged.valence, -0.8);
+
+        let calmer = Homeostasis::relax(&angry, &personality, &config, 100);
+        assert!((calmer.valence - -0.8 * (-1.0_f32).exp()).abs() < 1e-6);
+        assert!(calmer.arousal < 0.6 && calmer.arousal > 0.0);
+    }
+
+    #[test]
+    fn test_time_to_baseline() {
+        let personality = PersonalityTraits::new();
+        let config = HomeostasisConfig {
+            rate: 0.01,
+            tolerance: 0.05,
+        };
+        let angry = EmotionPrediction::new(-0.8, 0.6);
+
+        let minutes = Homeostasis::time_to_baseline(&angry, &personality, &config).unwrap();
+        assert_eq!(minutes, 278);
+
+        let settled = Homeostasis::relax(&angry, &personality, &config, minutes);
+        assert!(settled.valence.abs() <= config.tolerance);
+
+        let calm = EmotionPrediction::new(0.02, -0.01);
+        assert_eq!(Homeostasis::time_to_baseline(&calm, &personality, &config), Some(0));
+
+        let no_homeostasis = HomeostasisConfig { rate: 0.0, ..config };
+        assert_eq!(
+            Homeostasis::time_to_baseline(&angry, &personality, &no_homeostasis),
+            None
+        );
+    }
+}
@@ -0,0 +1,5 @@
+pub mod homeostasis;
+pub mod state;
+
+pub use homeostasis::Homeostasis;
+pub use state::{AffectState, AffectStore};
@@ -0,0 +1,195 @@
+use crate::api::types::NpcId;
+use crate::config::MoodConfig;
+use crate::{EmotionPrediction, GameTime, PersonalityTraits};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref NPC_AFFECT: Mutex<HashMap<NpcId, AffectState>> = Mutex::new(HashMap::new());
+}
+
+/// Fast `emotion` that spikes on interactions and slow `mood` that follows it, both relaxing
+/// towards the personality baseline as game time passes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AffectState {
+    pub emotion: EmotionPrediction,
+    pub mood: EmotionPrediction,
+    pub updated_at: GameTime,
+}
+
+impl AffectState {
+    pub fn baseline(personality: &PersonalityTraits, now: GameTime) -> Self {
+        let baseline =
+            EmotionPrediction::new(personality.valence, personality.arousal).with_dominance(personality.dominance);
+
+        Self {
+            emotion: baseline.clone(),
+            mood: baseline,
+            updated_at: now,
+        }
+    }
+
+    /// State after the time elapsed between `updated_at` and `now`.
+    pub fn advanced(&self, config: &MoodConfig, personality: &PersonalityTraits, now: GameTime) -> Self {
+        let elapsed = (now - self.updated_at).max(0) as f32;
+
+        let emotion_retention = (-elapsed / config.emotion_time_constant).exp();
+        let mood_retention = (-elapsed / config.mood_time_constant).exp();
+        let baseline_retention = (-elapsed / config.baseline_time_constant).exp();
+
+        let relax = |emotion: f32, mood: f32, baseline: f32| {
+            let mood = mood + (emotion - mood) * (1.0 - mood_retention);
+            let mood = baseline + (mood - baseline) * baseline_retention;
+            let emotion = mood + (emotion - mood) * emotion_retention;
+
+            (emotion, mood)
+        };
+
+        let (emotion_valence, mood_valence) = relax(self.emotion.valence, self.mood.valence, personality.valence);
+        let (emotion_arousal, mood_arousal) = relax(self.emotion.arousal, self.mood.arousal, personality.arousal);
+        let (emotion_dominance, mood_dominance) = match personality.dominance {
+            Some(baseline) => {
+                let (emotion, mood) = relax(
+                    self.emotion.dominance.unwrap_or(baseline),
+                    self.mood.dominance.unwrap_or(baseline),
+                    baseline,
+                );
+                (Some(emotion), Some(mood))
+            }
+            None => (None, None),
+        };
+
+        Self {
+            emotion: EmotionPrediction::new(emotion_valence, emotion_arousal).with_dominance(emotion_dominance),
+            mood: EmotionPrediction::new(mood_valence, mood_arousal).with_dominance(mood_dominance),
+            updated_at: now.max(self.updated_at),
+        }
+    }
+
+    /// The emotion jumps to the interaction result while the mood only moves by `interaction_impulse`.
+    pub fn apply_interaction(&mut self, config: &MoodConfig, emotion: &EmotionPrediction) {
+        let impulse = config.interaction_impulse;
+        let nudge = |mood: f32, emotion: f32| mood + (emotion - mood) * impulse;
+
+        self.mood = EmotionPrediction::new(
+            nudge(self.mood.valence, emotion.valence),
+            nudge(self.mood.arousal, emotion.arousal),
+        )
+        .with_dominance(match (self.mood.dominance, emotion.dominance) {
+            (Some(mood), Some(emotion)) => Some(nudge(mood, emotion)),
+            (mood, emotion) => emotion.or(mood),
+        });
+        self.emotion = emotion.clone();
+    }
+}
+
+pub struct AffectStore;
+
+impl AffectStore {
+    pub fn get(
+        npc_id: &NpcId,
+        config: &MoodConfig,
+        personality: &PersonalityTraits,
+        now: GameTime,
+    ) -> Result<AffectState, String> {
+        let npc_affect = NPC_AFFECT.lock().map_err(|_| "Failed to acquire affect lock")?;
+
+        Ok(match npc_affect.get(npc_id) {
+            Some(state) => state.advanced(config, personality, now),
+            None => AffectState::baseline(personality, now),
+        })
+    }
+
+    /// The state as last stored, without relaxing it to the current time. `None` at the baseline.
+    pub fn get_stored(npc_id: &NpcId) -> Result<Option<AffectState>, String> {
+        let npc_affect = NPC_AFFECT.lock().map_err(|_| "Failed to acquire affect lock")?;
+
+        Ok(npc_affect.get(npc_id).cloned())
+    }
+
+    pub fn set(npc_id: &NpcId, state: AffectState) -> Result<(), String> {
+        let mut npc_affect = NPC_AFFECT.lock().map_err(|_| "Failed to acquire affect lock")?;
+
+        npc_affect.insert(npc_id.clone(), state);
+
+        Ok(())
+    }
+
+    pub fn remove_npc(npc_id: &NpcId) -> Result<(), String> {
+        let mut npc_affect = NPC_AFFECT.lock().map_err(|_| "Failed to acquire affect lock")?;
+
+        npc_affect.remove(npc_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AffectState, AffectStore};
+    use crate::config::MoodConfig;
+    use crate::{EmotionPrediction, PersonalityTraits};
+
+    #[test]
+    fn test_affect_state_starts_at_baseline() {
+        let personality = PersonalityTraits {
+            valence: 0.3,
+            arousal: -0.2,
+            ..PersonalityTraits::new()
+        };
+        let state = AffectState::baseline(&personality, 10);
+
+        assert_eq!(state.emotion.valence, 0.3);
+        assert_eq!(state.mood.arousal, -0.2);
+        assert_eq!(state.updated_at, 10);
+    }
+
+    #[test]
+    fn test_emotion_spikes_and_mood_lags() {
+        let config = MoodConfig::default();
+        let personality = PersonalityTraits::new();
+        let mut state = AffectState::baseline(&personality, 0);
+
+        state.apply_interaction(&config, &EmotionPrediction::new(-0.8, 0.6));
+
+        assert_eq!(state.emotion.valence, -0.8);
+        assert!(state.mood.valence < 0.0);
+        assert!(state.mood.valence > -0.8);
+    }
+
+    #[test]
+    fn test_emotion_and_mood_return_to_baseline() {
+        let config = MoodConfig::default();
+        let personality = PersonalityTraits::new();
+        let mut state = AffectState::baseline(&personality, 0);
+        state.apply_interaction(&config, &EmotionPrediction::new(-0.8, 0.6));
+
+        let soon = state.advanced(&config, &personality, 30);
+        assert!(soon.emotion.valence > -0.8);
+        assert!(soon.mood.valence < state.mood.valence);
+
+        let later = state.advanced(&config, &personality, 60 * 24 * 30);
+        assert!(later.emotion.valence.abs() < 0.01);
+        assert!(later.mood.valence.abs() < 0.01);
+    }
+
+    #[test]
+    fn test_affect_store_roundtrip() {
+        let npc_id = "affect-npc-store".to_string();
+        let config = MoodConfig::default();
+        let personality = PersonalityTraits::new();
+
+        let mut state = AffectStore::get(&npc_id, &config, &personality, 0).unwrap();
+        state.apply_interaction(&config, &EmotionPrediction::new(0.9, 0.2));
+        AffectStore::set(&npc_id, state).unwrap();
+
+        let stored = AffectStore::get(&npc_id, &config, &personality, 0).unwrap();
+        assert_eq!(stored.emotion.valence, 0.9);
+
+        AffectStore::remove_npc(&npc_id).unwrap();
+        let reset = AffectStore::get(&npc_id, &config, &personality, 0).unwrap();
+        assert_eq!(reset.emotion.valence, 0.0);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::EmotionPrediction;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Something that happened to the NPC that is not dialogue, such as being robbed or receiving a gift.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameEvent {
+    pub kind: String,
+    #[serde(default)]
+    pub source_id: Option<String>,
+    #[serde(default)]
+    pub target_id: Option<String>,
+    #[serde(default = "default_magnitude")]
+    pub magnitude: f32,
+}
+
+fn default_magnitude() -> f32 {
+    1.0
+}
+
+impl GameEvent {
+    pub fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.to_string(),
+            source_id: None,
+            target_id: None,
+            magnitude: default_magnitude(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let mut description = format!("[event] {}", self.kind);
+
+        if let Some(source_id) = &self.source_id {
+            description.push_str(&format!(" by {}", source_id));
+        }
+        if let Some(target_id) = &self.target_id {
+            description.push_str(&format!(" on {}", target_id));
+        }
+
+        format!("{} (magnitude {})", description, self.magnitude)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.kind.trim().is_empty() {
+            return Err("Event kind must not be empty".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.magnitude) {
+            return Err(format!(
+                "Event magnitude has value {}, but must be between 0.0 and 1.0",
+                self.magnitude
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// OCC-style appraisal of an event kind.
+///
+/// `desirability` is how good the outcome is for the NPC, `praiseworthiness` how much the actor is
+/// to be praised (positive) or blamed (negative), and `expectedness` how predictable the event is.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventAppraisal {
+    pub desirability: f32,
+    #[serde(default)]
+    pub praiseworthiness: f32,
+    #[serde(default = "default_expectedness")]
+    pub expectedness: f32,
+}
+
+fn default_expectedness() -> f32 {
+    0.5
+}
+
+impl EventAppraisal {
+    pub fn new(desirability: f32, praiseworthiness: f32, expectedness: f32) -> Self {
+        Self {
+            desirability,
+            praiseworthiness,
+            expectedness,
+        }
+    }
+
+    /// Maps the appraisal onto the circumplex, scaled by the event magnitude:
+    /// - valence: `0.7 * desirability + 0.3 * praiseworthiness`
+    /// - arousal: `0.6 * |desirability| + 0.2 * blame + 0.4 * (1 - expectedness)`, so surprises and blame agitate
+    /// - dominance: `0.3 * desirability + 0.5 * blame`, so blamed harm feels like anger and unblamed harm like helplessness
+    pub fn to_emotion(&self, magnitude: f32, with_dominance: bool) -> EmotionPrediction {
+        let blame = (-self.praiseworthiness).max(0.0);
+
+        let valence = magnitude * (0.7 * self.desirability + 0.3 * self.praiseworthiness);
+        let arousal = magnitude * (0.6 * self.desirability.abs() + 0.2 * blame + 0.4 * (1.0 - self.expectedness));
+        let dominance = magnitude * (0.3 * self.desirability + 0.5 * blame);
+
+        EmotionPrediction::new(valence.clamp(-1.0, 1.0), arousal.clamp(-1.0, 1.0))
+            .with_dominance(with_dominance.then(|| dominance.clamp(-1.0, 1.0)))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let values = [
+            ("desirability", self.desirability, -1.0, 1.0),
+            ("praiseworthiness", self.praiseworthiness, -1.0, 1.0),
+            ("expectedness", self.expectedness, 0.0, 1.0),
+        ];
+
+        for (name, value, min, max) in values {
+            if !(min..=max).contains(&value) {
+                return Err(format!(
+                    "Event appraisal '{}' has value {}, but must be between {} and {}",
+                    name, value, min, max
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+lazy_static! {
+    static ref DEFAULT_EVENT_TABLE: HashMap<String, EventAppraisal> = default_event_table();
+}
+
+pub fn default_event_table() -> HashMap<String, EventAppraisal> {
+    [
+        ("gift_received", EventAppraisal::new(0.6, 0.6, 0.3)),
+        ("helped", EventAppraisal::new(0.5, 0.7, 0.4)),
+        ("praised", EventAppraisal::new(0.4, 0.5, 0.5)),
+        ("quest_completed", EventAppraisal::new(0.7, 0.3, 0.6)),
+        ("rescued", EventAppraisal::new(0.9, 0.8, 0.1)),
+        ("insulted", EventAppraisal::new(-0.4, -0.6, 0.5)),
+        ("robbed", EventAppraisal::new(-0.7, -0.8, 0.2)),
+        ("attacked", EventAppraisal::new(-0.8, -0.9, 0.2)),
+        ("betrayed", EventAppraisal::new(-0.8, -1.0, 0.1)),
+        ("threatened", EventAppraisal::new(-0.6, -0.5, 0.3)),
+        ("ally_died", EventAppraisal::new(-0.9, 0.0, 0.2)),
+        ("lost_item", EventAppraisal::new(-0.3, 0.0, 0.4)),
+    ]
+    .into_iter()
+    .map(|(kind, appraisal)| (kind.to_string(), appraisal))
+    .collect()
+}
+
+/// Event kinds in `table` are added to the built-in kinds, replacing any with the same name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventConfig {
+    pub table: HashMap<String, EventAppraisal>,
+}
+
+impl EventConfig {
+    pub fn appraisal(&self, kind: &str) -> Option<EventAppraisal> {
+        self.table.get(kind).or_else(|| DEFAULT_EVENT_TABLE.get(kind)).cloned()
+    }
+
+    pub fn appraise(&self, event: &GameEvent, with_dominance: bool) -> Result<EmotionPrediction, String> {
+        event.validate()?;
+
+        let appraisal = self
+            .appraisal(&event.kind)
+            .ok_or_else(|| format!("Unknown event kind '{}'", event.kind))?;
+
+        Ok(appraisal.to_emotion(event.magnitude, with_dominance))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        for (kind, appraisal) in &self.table {
+            appraisal
+                .validate()
+                .map_err(|e| format!("Event kind '{}': {}", kind, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventAppraisal, EventConfig, GameEvent};
+
+    #[test]
+    fn test_builtin_events_appraisal() {
+        let config = EventConfig::default();
+
+        let gift = config.appraise(&GameEvent::new("gift_received"), false).unwrap();
+        assert!(gift.valence > 0.0);
+        assert!(gift.dominance.is_none());
+
+        let robbed = config.appraise(&GameEvent::new("robbed"), true).unwrap();
+        let ally_died = config.appraise(&GameEvent::new("ally_died"), true).unwrap();
+        assert!(robbed.valence < 0.0);
+        assert!(robbed.dominance.unwrap() > ally_died.dominance.unwrap());
+    }
+
+    #[test]
+    fn test_magnitude_and_surprise() {
+        let config = EventConfig::default();
+        let mild = GameEvent {
+            magnitude: 0.2,
+            ..GameEvent::new("robbed")
+        };
+
+        let full = config.appraise(&GameEvent::new("robbed"), false).unwrap();
+        let weak = config.appraise(&mild, false).unwrap();
+        assert!(weak.valence.abs() < full.valence.abs());
+
+        let expected = EventAppraisal::new(0.0, 0.0, 1.0).to_emotion(1.0, false);
+        let surprise = EventAppraisal::new(0.0, 0.0, 0.0).to_emotion(1.0, false);
+        assert!(surprise.arousal > expected.arousal);
+    }
+
+    #[test]
+    fn test_custom_table_overrides_builtin() {
+        let config: EventConfig = serde_json::from_str(
+            r#"{"table": {"robbed": {"desirability": 0.2}, "festival": {"desirability": 0.5, "expectedness": 1.0}}}"#,
+        )
+        .unwrap();
+
+        assert!(config.validate().is_ok());
+        assert!(config.appraise(&GameEvent::new("robbed"), false).unwrap().valence > 0.0);
+        assert!(config.appraise(&GameEvent::new("festival"), false).is_ok());
+        assert!(config.appraise(&GameEvent::new("gift_received"), false).is_ok());
+        assert!(config.appraise(&GameEvent::new("unknown"), false).is_err());
+    }
+
+    #[test]
+    fn test_event_validation() {
+        let invalid = GameEvent {
+            magnitude: 1.5,
+            ..GameEvent::new("robbed")
+        };
+        assert!(invalid.validate().is_err());
+        assert!(GameEvent::new(" ").validate().is_err());
+        assert!(EventAppraisal::new(0.0, 0.0, 2.0).validate().is_err());
+    }
+}
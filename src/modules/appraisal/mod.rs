@@ -0,0 +1,5 @@
+pub mod event;
+pub mod observation;
+
+pub use event::{EventAppraisal, EventConfig, GameEvent};
+pub use observation::ObservationConfig;
@@ -0,0 +1,130 @@
+use crate::EmotionPrediction;
+use serde::{Deserialize, Serialize};
+
+/// How an NPC reacts to interactions it witnesses between third parties.
+///
+/// `empathy` is how much it shares what happens to a stranger, and `schadenfreude` how much it
+/// enjoys the misfortune of someone it dislikes. Friends are always felt for in full.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ObservationConfig {
+    pub empathy: f32,
+    pub schadenfreude: f32,
+}
+
+impl Default for ObservationConfig {
+    fn default() -> Self {
+        Self {
+            empathy: 0.3,
+            schadenfreude: 0.5,
+        }
+    }
+}
+
+impl ObservationConfig {
+    /// Share of the target's experience felt by the observer, from `-schadenfreude` for enemies,
+    /// through `empathy` for strangers, to 1.0 for friends. `affinity` is how much more pleasant the
+    /// observer feels about the target than its own baseline.
+    pub fn resonance(&self, affinity: f32) -> f32 {
+        let affinity = affinity.clamp(-1.0, 1.0);
+
+        if affinity >= 0.0 {
+            self.empathy + (1.0 - self.empathy) * affinity
+        } else {
+            self.empathy + (self.empathy + self.schadenfreude) * affinity
+        }
+    }
+
+    /// Emotion of the observer for an interaction whose emotion for the target is `target_emotion`.
+    /// Valence and dominance follow the resonance, so humbling an enemy makes the observer feel in
+    /// control; arousal is shared whether the observer sympathises or gloats.
+    pub fn vicarious_emotion(&self, target_emotion: &EmotionPrediction, affinity: f32) -> EmotionPrediction {
+        let resonance = self.resonance(affinity);
+
+        EmotionPrediction::new(
+            (target_emotion.valence * resonance).clamp(-1.0, 1.0),
+            (target_emotion.arousal * resonance.abs()).clamp(-1.0, 1.0),
+        )
+        .with_dominance(
+            target_emotion
+                .dominance
+                .map(|dominance| (dominance * resonance).clamp(-1.0, 1.0)),
+        )
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.empathy) {
+            return Err(format!(
+                "Observation empathy has value {}, but must be between 0.0 and 1.0",
+                self.empathy
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&self.schadenfreude) {
+            return Err(format!(
+                "Observation schadenfreude has value {}, but must be between 0.0 and 1.0",
+                self.schadenfreude
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ObservationConfig;
+    use crate::EmotionPrediction;
+
+    #[test]
+    fn test_resonance_range() {
+        let config = ObservationConfig::default();
+
+        assert_eq!(config.resonance(1.0), 1.0);
+        assert_eq!(config.resonance(0.0), 0.3);
+        assert!((config.resonance(-1.0) - -0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_empathy_and_schadenfreude() {
+        let config = ObservationConfig::default();
+        let attacked = EmotionPrediction::new(-0.8, 0.6);
+
+        let friend = config.vicarious_emotion(&attacked, 0.8);
+        let stranger = config.vicarious_emotion(&attacked, 0.0);
+        let enemy = config.vicarious_emotion(&attacked, -0.8);
+
+        assert!(friend.valence < stranger.valence);
+        assert!(stranger.valence < 0.0);
+        assert!(enemy.valence > 0.0);
+        assert!(enemy.arousal > 0.0);
+    }
+
+    #[test]
+    fn test_vicarious_dominance_follows_resonance() {
+        let config = ObservationConfig::default();
+        let humiliated = EmotionPrediction::new(-0.6, 0.4).with_dominance(Some(-0.8));
+
+        let friend = config.vicarious_emotion(&humiliated, 1.0);
+        let enemy = config.vicarious_emotion(&humiliated, -1.0);
+
+        assert_eq!(friend.dominance, Some(-0.8));
+        assert!((enemy.dominance.unwrap() - 0.4).abs() < 1e-6);
+        assert_eq!(
+            config
+                .vicarious_emotion(&EmotionPrediction::new(-0.6, 0.4), -1.0)
+                .dominance,
+            None
+        );
+    }
+
+    #[test]
+    fn test_observation_validate() {
+        assert!(ObservationConfig::default().validate().is_ok());
+        let invalid = ObservationConfig {
+            schadenfreude: 1.5,
+            ..ObservationConfig::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
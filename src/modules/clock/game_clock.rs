@@ -0,0 +1,87 @@
+use crate::api::types::NpcId;
+use crate::MemoryStore;
+
+/// Game time expressed in minutes.
+pub type GameTime = i64;
+
+pub struct GameClock;
+
+impl GameClock {
+    pub fn now(npc_id: &NpcId) -> Result<GameTime, String> {
+        MemoryStore::now(npc_id)
+    }
+
+    pub fn set(npc_id: &NpcId, time: GameTime) -> Result<GameTime, String> {
+        MemoryStore::set_now(npc_id, time)?;
+
+        Ok(time)
+    }
+
+    pub fn advance(npc_id: &NpcId, minutes: GameTime) -> Result<GameTime, String> {
+        if minutes < 0 {
+            return Err(format!(
+                "Cannot advance time by a negative amount ({} minutes)",
+                minutes
+            ));
+        }
+
+        MemoryStore::update_now(npc_id, |now| Ok(now + minutes))
+    }
+
+    /// Moves the NPC clock to `time` unless the clock is already past it.
+    pub fn advance_to(npc_id: &NpcId, time: GameTime) -> Result<GameTime, String> {
+        MemoryStore::update_now(npc_id, |now| Ok(now.max(time)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameClock;
+    use crate::{MemoryRecord, MemoryStore};
+
+    #[test]
+    fn test_game_clock_starts_at_zero() {
+        let npc_id = "clock-npc-zero".to_string();
+        assert_eq!(GameClock::now(&npc_id).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_game_clock_advance_ages_memories() {
+        let npc_id = "clock-npc-advance".to_string();
+
+        MemoryStore::insert(
+            &npc_id,
+            MemoryRecord {
+                id: "clock-1".to_string(),
+                source_id: "player".to_string(),
+                content: "Hello".to_string(),
+                valence: 0.5,
+                arousal: 0.1,
+                dominance: None,
+                occurred_at: 0,
+                heard_from: None,
+                past_time: None,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(GameClock::advance(&npc_id, 30).unwrap(), 30);
+        assert_eq!(GameClock::advance(&npc_id, 15).unwrap(), 45);
+
+        let now = GameClock::now(&npc_id).unwrap();
+        assert_eq!(MemoryStore::get_all(&npc_id).unwrap()[0].age(now), 45);
+
+        assert!(GameClock::advance(&npc_id, -5).is_err());
+    }
+
+    #[test]
+    fn test_game_clock_advance_to_never_goes_backwards() {
+        let npc_id = "clock-npc-advance-to".to_string();
+
+        assert_eq!(GameClock::advance_to(&npc_id, 100).unwrap(), 100);
+        assert_eq!(GameClock::advance_to(&npc_id, 50).unwrap(), 100);
+
+        GameClock::set(&npc_id, 10).unwrap();
+        assert_eq!(GameClock::now(&npc_id).unwrap(), 10);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod game_clock;
+
+pub use game_clock::{GameClock, GameTime};
@@ -0,0 +1,353 @@
+use crate::modules::emotion::manifest::{ManifestFile, ModelManifest};
+use crate::EmotionPredictorError;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use ureq::tls::{TlsConfig, TlsProvider};
+use ureq::Agent;
+
+pub struct ModelDownloader {
+    base_url: String,
+    agent: Agent,
+}
+
+impl ModelDownloader {
+    pub const DEFAULT_BASE_URL: &'static str =
+        "https://huggingface.co/Mavdol/NPC-Valence-Arousal-Prediction-ONNX/resolve/main";
+
+    pub fn new(base_url: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_connect(Some(Duration::from_secs(30)))
+            .tls_config(TlsConfig::builder().provider(TlsProvider::NativeTls).build())
+            .build()
+            .into();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            agent,
+        }
+    }
+
+    /// Downloads every file of `manifest` into `model_dir`, skipping the ones already matching it.
+    pub fn download(&self, manifest: &ModelManifest, model_dir: &Path) -> Result<(), EmotionPredictorError> {
+        std::fs::create_dir_all(model_dir)?;
+
+        for file in &manifest.files {
+            self.download_file(file, model_dir)?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes to `<name>.part` and renames once it matches the manifest. A leftover `.part` file
+    /// from an interrupted download is resumed with a range request, and downloaded again in full
+    /// if the result does not match. Unpinned files cannot be checked, so they are always downloaded
+    /// in full; loading them fails unless verify_integrity is off.
+    fn download_file(&self, file: &ManifestFile, model_dir: &Path) -> Result<(), EmotionPredictorError> {
+        let target_path = model_dir.join(&file.name);
+        let part_path = Self::part_path(&target_path);
+
+        if !file.is_pinned() {
+            Self::remove_part(&part_path)?;
+            self.fetch_into(&file.name, &part_path, 0)?;
+            std::fs::rename(&part_path, &target_path)?;
+            return Ok(());
+        }
+
+        if file.check(&target_path).is_ok() {
+            return Ok(());
+        }
+
+        let resume_from = std::fs::metadata(&part_path)
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+
+        if resume_from > 0 {
+            if file.size.is_none_or(|size| resume_from < size) {
+                self.fetch_into(&file.name, &part_path, resume_from)?;
+            }
+
+            if file.check(&part_path).is_ok() {
+                std::fs::rename(&part_path, &target_path)?;
+                return Ok(());
+            }
+
+            // The leftover part was stale or corrupted: start over.
+            Self::remove_part(&part_path)?;
+        }
+
+        self.fetch_into(&file.name, &part_path, 0)?;
+
+        if let Err(reason) = file.check(&part_path) {
+            Self::remove_part(&part_path)?;
+            return Err(EmotionPredictorError::ModelLoading(format!(
+                "Rejected download of {}: {}",
+                file.name, reason
+            )));
+        }
+
+        std::fs::rename(&part_path, &target_path)?;
+        Ok(())
+    }
+
+    fn remove_part(part_path: &Path) -> Result<(), EmotionPredictorError> {
+        match std::fs::remove_file(part_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn fetch_into(&self, name: &str, part_path: &Path, resume_from: u64) -> Result<(), EmotionPredictorError> {
+        let url = self.url(name);
+        eprintln!("Downloading {}...", name);
+
+        let mut request = self.agent.get(&url);
+        if resume_from > 0 {
+            request = request.header("Range", format!("bytes={}-", resume_from));
+        }
+
+        let response = request
+            .call()
+            .map_err(|e| EmotionPredictorError::ModelLoading(format!("Failed to download {}: {}", name, e)))?;
+
+        // Servers without range support answer 200 with the whole file.
+        let append = match response.status().as_u16() {
+            206 => true,
+            200 => false,
+            // The leftover part may already hold the whole file; the caller checks it.
+            416 if resume_from > 0 => return Ok(()),
+            status => {
+                return Err(EmotionPredictorError::ModelLoading(format!(
+                    "Failed to download {}: HTTP {}",
+                    name, status
+                )))
+            }
+        };
+
+        let mut part_file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(part_path)?;
+
+        std::io::copy(&mut response.into_body().into_reader(), &mut part_file)
+            .map_err(|e| EmotionPredictorError::ModelLoading(format!("Failed to download {}: {}", name, e)))?;
+        part_file.sync_all()?;
+
+        Ok(())
+    }
+
+    fn url(&self, name: &str) -> String {
+        format!("{}/{}", self.base_url, name)
+    }
+
+    fn part_path(target_path: &Path) -> PathBuf {
+        let mut part_path = target_path.as_os_str().to_owned();
+        part_path.push(".part");
+        PathBuf::from(part_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ModelDownloader;
+    use crate::modules::emotion::manifest::{sha256_file, ManifestFile, ModelManifest};
+    use crate::EmotionPredictorError;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+
+    /// Minimal HTTP/1.1 server with range support. Returns its base URL and the received `Range` headers.
+    fn serve(files: HashMap<String, Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let received = ranges.clone();
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line
+                    .split_whitespace()
+                    .nth(1)
+                    .unwrap_or("/")
+                    .trim_start_matches('/');
+
+                let mut range_start = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        received.lock().unwrap().push(value.trim().to_string());
+                        range_start = value.trim().trim_end_matches('-').parse::<usize>().ok();
+                    }
+                }
+
+                let mut stream = stream;
+                let (status, body) = match (files.get(path), range_start) {
+                    (Some(content), Some(start)) if start >= content.len() => ("416 Range Not Satisfiable", Vec::new()),
+                    (Some(content), Some(start)) => ("206 Partial Content", content[start..].to_vec()),
+                    (Some(content), None) => ("200 OK", content.clone()),
+                    (None, _) => ("404 Not Found", b"Not Found".to_vec()),
+                };
+
+                let header = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    status,
+                    body.len()
+                );
+                let _ = stream.write_all(header.as_bytes());
+                let _ = stream.write_all(&body);
+            }
+        });
+
+        (base_url, ranges)
+    }
+
+    fn manifest_for(files: &HashMap<String, Vec<u8>>, dir: &Path) -> ModelManifest {
+        let entries = files
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(format!("hash_{}", name));
+                std::fs::write(&path, content).unwrap();
+                let sha256 = sha256_file(&path).unwrap();
+                std::fs::remove_file(&path).unwrap();
+
+                ManifestFile {
+                    name: name.clone(),
+                    size: Some(content.len() as u64),
+                    sha256: Some(sha256),
+                }
+            })
+            .collect();
+
+        ModelManifest {
+            version: "v0.0.1".to_string(),
+            files: entries,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+            max_sequence_length: 512,
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_download_verifies_files() {
+        let dir = temp_dir("test_download_models");
+        let files = HashMap::from([("model.onnx".to_string(), vec![7u8; 4096])]);
+        let manifest = manifest_for(&files, &dir);
+        let (base_url, _) = serve(files);
+
+        ModelDownloader::new(&base_url).download(&manifest, &dir).unwrap();
+
+        assert_eq!(std::fs::read(dir.join("model.onnx")).unwrap(), vec![7u8; 4096]);
+        assert!(!dir.join("model.onnx.part").exists());
+        assert!(manifest.verify_files(&dir).is_ok());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_download_unpinned_file() {
+        let dir = temp_dir("test_unpinned_models");
+        let files = HashMap::from([("vocab.txt".to_string(), b"[PAD]\n[UNK]".to_vec())]);
+        let mut manifest = manifest_for(&files, &dir);
+        manifest.files[0].size = None;
+        manifest.files[0].sha256 = None;
+        let (base_url, ranges) = serve(files);
+
+        std::fs::write(dir.join("vocab.txt.part"), b"[PAD]\n[UNK]garbage").unwrap();
+        ModelDownloader::new(&base_url).download(&manifest, &dir).unwrap();
+
+        assert_eq!(std::fs::read(dir.join("vocab.txt")).unwrap(), b"[PAD]\n[UNK]".to_vec());
+        assert!(ranges.lock().unwrap().is_empty());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_download_resumes_partial_file() {
+        let dir = temp_dir("test_resume_models");
+        let content: Vec<u8> = (0..5000).map(|i| (i % 251) as u8).collect();
+        let files = HashMap::from([("model.onnx".to_string(), content.clone())]);
+        let manifest = manifest_for(&files, &dir);
+        let (base_url, ranges) = serve(files);
+
+        std::fs::write(dir.join("model.onnx.part"), &content[..1200]).unwrap();
+        ModelDownloader::new(&base_url).download(&manifest, &dir).unwrap();
+
+        assert_eq!(std::fs::read(dir.join("model.onnx")).unwrap(), content);
+        assert_eq!(*ranges.lock().unwrap(), vec!["1200-".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_download_replaces_stale_part() {
+        let dir = temp_dir("test_stale_part_models");
+        let content: Vec<u8> = (0..3000).map(|i| (i % 199) as u8).collect();
+        let files = HashMap::from([("model.onnx".to_string(), content.clone())]);
+        let manifest = manifest_for(&files, &dir);
+        let (base_url, ranges) = serve(files);
+
+        // A garbage part as long as the file would otherwise be taken as complete.
+        std::fs::write(dir.join("model.onnx.part"), vec![0u8; 3000]).unwrap();
+        ModelDownloader::new(&base_url).download(&manifest, &dir).unwrap();
+        assert_eq!(std::fs::read(dir.join("model.onnx")).unwrap(), content);
+
+        // A garbage part that the server resumes is downloaded again in full.
+        std::fs::remove_file(dir.join("model.onnx")).unwrap();
+        std::fs::write(dir.join("model.onnx.part"), vec![0u8; 1000]).unwrap();
+        ModelDownloader::new(&base_url).download(&manifest, &dir).unwrap();
+        assert_eq!(std::fs::read(dir.join("model.onnx")).unwrap(), content);
+        assert_eq!(*ranges.lock().unwrap(), vec!["1000-".to_string()]);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_download_rejects_checksum_mismatch() {
+        let dir = temp_dir("test_checksum_models");
+        let files = HashMap::from([("model.onnx".to_string(), vec![1u8; 2048])]);
+        let mut manifest = manifest_for(&files, &dir);
+        manifest.files[0].sha256 = Some("0".repeat(64));
+        let (base_url, _) = serve(files);
+
+        let result = ModelDownloader::new(&base_url).download(&manifest, &dir);
+
+        assert!(matches!(result, Err(EmotionPredictorError::ModelLoading(msg)) if msg.contains("SHA-256")));
+        assert!(!dir.join("model.onnx").exists());
+        assert!(!dir.join("model.onnx.part").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_download_reports_http_errors() {
+        let dir = temp_dir("test_http_error_models");
+        let manifest = manifest_for(&HashMap::from([("model.onnx".to_string(), vec![1u8; 16])]), &dir);
+        let (base_url, _) = serve(HashMap::new());
+
+        let result = ModelDownloader::new(&base_url).download(&manifest, &dir);
+
+        assert!(matches!(result, Err(EmotionPredictorError::ModelLoading(msg)) if msg.contains("HTTP 404")));
+        assert!(!dir.join("model.onnx").exists());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::EmotionPrediction;
+use serde::{Deserialize, Serialize};
+
+pub const NEUTRAL_LABEL: &str = "neutral";
+
+/// A named point of the circumplex. Regions are matched by direction, so only the angle of
+/// `valence`/`arousal` matters; `dominance` separates emotions that share a direction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmotionRegion {
+    pub name: String,
+    pub valence: f32,
+    pub arousal: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominance: Option<f32>,
+}
+
+impl EmotionRegion {
+    pub fn new(name: impl Into<String>, valence: f32, arousal: f32) -> Self {
+        Self {
+            name: name.into(),
+            valence,
+            arousal,
+            dominance: None,
+        }
+    }
+
+    pub fn with_dominance(mut self, dominance: f32) -> Self {
+        self.dominance = Some(dominance);
+        self
+    }
+
+    fn distance(&self, direction: (f32, f32), dominance: Option<f32>) -> f32 {
+        let length = (self.valence * self.valence + self.arousal * self.arousal)
+            .sqrt()
+            .max(f32::EPSILON);
+        let valence_delta = direction.0 - self.valence / length;
+        let arousal_delta = direction.1 - self.arousal / length;
+        let dominance_delta = match (dominance, self.dominance) {
+            (Some(dominance), Some(region_dominance)) => dominance - region_dominance,
+            _ => 0.0,
+        };
+
+        valence_delta * valence_delta + arousal_delta * arousal_delta + dominance_delta * dominance_delta
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmotionLabel {
+    pub primary: String,
+    pub secondary: Option<String>,
+    pub intensity: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LabelingConfig {
+    /// Below this intensity the primary label is `neutral`.
+    pub neutral_radius: f32,
+    pub regions: Vec<EmotionRegion>,
+}
+
+impl LabelingConfig {
+    /// Regions placed at the angles of Russell's circumplex (1980). Anger and fear carry the
+    /// dominance values from Mehrabian's PAD scale.
+    pub fn russell_regions() -> Vec<EmotionRegion> {
+        vec![
+            EmotionRegion::new("happy", 0.99, 0.14),
+            EmotionRegion::new("delighted", 0.91, 0.42),
+            EmotionRegion::new("excited", 0.66, 0.75),
+            EmotionRegion::new("tense", -0.03, 1.0),
+            EmotionRegion::new("angry", -0.16, 0.99).with_dominance(0.25),
+            EmotionRegion::new("afraid", -0.45, 0.89).with_dominance(-0.43),
+            EmotionRegion::new("frustrated", -0.78, 0.63),
+            EmotionRegion::new("miserable", -0.99, -0.16),
+            EmotionRegion::new("sad", -0.88, -0.47),
+            EmotionRegion::new("bored", -0.47, -0.88),
+            EmotionRegion::new("tired", -0.03, -1.0),
+            EmotionRegion::new("content", 0.75, -0.66),
+            EmotionRegion::new("serene", 0.86, -0.52),
+        ]
+    }
+
+    pub fn label(&self, emotion: &EmotionPrediction) -> EmotionLabel {
+        let magnitude = (emotion.valence * emotion.valence + emotion.arousal * emotion.arousal).sqrt();
+        let intensity = magnitude.min(1.0);
+
+        let direction = if magnitude > f32::EPSILON {
+            (emotion.valence / magnitude, emotion.arousal / magnitude)
+        } else {
+            (0.0, 0.0)
+        };
+
+        let mut ranked: Vec<(&EmotionRegion, f32)> = self
+            .regions
+            .iter()
+            .map(|region| (region, region.distance(direction, emotion.dominance)))
+            .collect();
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut names = ranked.into_iter().map(|(region, _)| region.name.clone());
+
+        if intensity < self.neutral_radius {
+            return EmotionLabel {
+                primary: NEUTRAL_LABEL.to_string(),
+                secondary: names.next(),
+                intensity,
+            };
+        }
+
+        EmotionLabel {
+            primary: names.next().unwrap_or_else(|| NEUTRAL_LABEL.to_string()),
+            secondary: names.next(),
+            intensity,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.neutral_radius) {
+            return Err(format!(
+                "Label neutral_radius has value {}, but must be between 0.0 and 1.0",
+                self.neutral_radius
+            ));
+        }
+
+        if self.regions.is_empty() {
+            return Err("Label regions cannot be empty".to_string());
+        }
+
+        for region in &self.regions {
+            if region.name.is_empty() {
+                return Err("Label region names cannot be empty".to_string());
+            }
+
+            let mut values = vec![("valence", region.valence), ("arousal", region.arousal)];
+            if let Some(dominance) = region.dominance {
+                values.push(("dominance", dominance));
+            }
+
+            for (name, value) in values {
+                if !(-1.0..=1.0).contains(&value) {
+                    return Err(format!(
+                        "Label region '{}' has {} {}, but must be between -1.0 and 1.0",
+                        region.name, name, value
+                    ));
+                }
+            }
+
+            if region.valence == 0.0 && region.arousal == 0.0 {
+                return Err(format!(
+                    "Label region '{}' must not sit at the centre of the circumplex",
+                    region.name
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for LabelingConfig {
+    fn default() -> Self {
+        Self {
+            neutral_radius: 0.1,
+            regions: Self::russell_regions(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmotionRegion, LabelingConfig, NEUTRAL_LABEL};
+    use crate::EmotionPrediction;
+
+    #[test]
+    fn test_default_labels() {
+        let labels = LabelingConfig::default();
+
+        assert_eq!(labels.label(&EmotionPrediction::new(0.8, 0.1)).primary, "happy");
+        assert_eq!(labels.label(&EmotionPrediction::new(-0.5, -0.5)).primary, "sad");
+        assert_eq!(labels.label(&EmotionPrediction::new(-0.3, -0.6)).primary, "bored");
+        assert_eq!(labels.label(&EmotionPrediction::new(0.5, -0.45)).primary, "content");
+    }
+
+    #[test]
+    fn test_label_intensity_and_neutral() {
+        let labels = LabelingConfig::default();
+
+        let label = labels.label(&EmotionPrediction::new(0.03, 0.04));
+        assert_eq!(label.primary, NEUTRAL_LABEL);
+        assert!(label.secondary.is_some());
+        assert!((label.intensity - 0.05).abs() < 1e-6);
+
+        let label = labels.label(&EmotionPrediction::new(1.0, 1.0));
+        assert_eq!(label.intensity, 1.0);
+    }
+
+    #[test]
+    fn test_dominance_separates_anger_and_fear() {
+        let labels = LabelingConfig::default();
+
+        let angry = labels.label(&EmotionPrediction::new(-0.3, 0.9).with_dominance(Some(0.4)));
+        let afraid = labels.label(&EmotionPrediction::new(-0.3, 0.9).with_dominance(Some(-0.5)));
+
+        assert_eq!(angry.primary, "angry");
+        assert_eq!(afraid.primary, "afraid");
+    }
+
+    #[test]
+    fn test_custom_regions() {
+        let labels = LabelingConfig {
+            neutral_radius: 0.0,
+            regions: vec![
+                EmotionRegion::new("friendly", 1.0, 0.0),
+                EmotionRegion::new("hostile", -1.0, 0.0),
+            ],
+        };
+
+        let label = labels.label(&EmotionPrediction::new(-0.4, 0.3));
+        assert_eq!(label.primary, "hostile");
+        assert_eq!(label.secondary, Some("friendly".to_string()));
+        assert!(labels.validate().is_ok());
+    }
+
+    #[test]
+    fn test_labeling_config_validate() {
+        assert!(LabelingConfig::default().validate().is_ok());
+
+        let mut labels = LabelingConfig::default();
+        labels.regions.clear();
+        assert!(labels.validate().is_err());
+
+        let mut labels = LabelingConfig::default();
+        labels.regions.push(EmotionRegion::new("void", 0.0, 0.0));
+        assert!(labels.validate().is_err());
+
+        let labels = LabelingConfig {
+            neutral_radius: 2.0,
+            ..LabelingConfig::default()
+        };
+        assert!(labels.validate().is_err());
+    }
+}
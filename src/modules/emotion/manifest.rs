@@ -0,0 +1,356 @@
+use crate::EmotionPredictorError;
+use serde::{Deserialize, Serialize};
+use sha2::digest::Output;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+
+/// Built-in manifests, keyed by model version. They ship with the crate so that neither the mirror
+/// nor the model directory can vouch for their own files.
+const BUILTIN_MANIFESTS: &[(&str, &str)] = &[("v0.0.1", include_str!("manifests/v0.0.1.json"))];
+
+/// A file of the model. A file without `sha256` is not pinned and never passes a check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestFile {
+    pub name: String,
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Lowercase hex SHA-256 of the file.
+    #[serde(default)]
+    pub sha256: Option<String>,
+}
+
+impl ManifestFile {
+    pub fn is_pinned(&self) -> bool {
+        self.sha256.is_some()
+    }
+
+    fn unpinned(&self) -> String {
+        format!(
+            "{} has no SHA-256 in the manifest, so it cannot be verified; pin it or disable verify_integrity",
+            self.name
+        )
+    }
+
+    /// Checks the pinned size and hash of `path`, returning why it does not match.
+    pub fn check(&self, path: &Path) -> Result<(), String> {
+        let metadata = std::fs::metadata(path).map_err(|_| format!("{} is missing", self.name))?;
+        let sha256 = self.sha256.as_ref().ok_or_else(|| self.unpinned())?;
+
+        if let Some(size) = self.size {
+            if metadata.len() != size {
+                return Err(format!(
+                    "{} is {} bytes, manifest expects {}",
+                    self.name,
+                    metadata.len(),
+                    size
+                ));
+            }
+        }
+
+        let hash = sha256_file(path).map_err(|e| format!("Failed to hash {}: {}", self.name, e))?;
+        if hash != *sha256 {
+            return Err(format!("{} does not match its SHA-256 checksum", self.name));
+        }
+
+        Ok(())
+    }
+
+    /// Same as `check` for a file loaded in memory.
+    pub fn check_bytes(&self, bytes: &[u8]) -> Result<(), String> {
+        let sha256 = self.sha256.as_ref().ok_or_else(|| self.unpinned())?;
+
+        if let Some(size) = self.size {
+            if bytes.len() as u64 != size {
+                return Err(format!(
+                    "{} is {} bytes, manifest expects {}",
+                    self.name,
+                    bytes.len(),
+                    size
+                ));
+            }
+        }
+
+        if to_hex(&Sha256::digest(bytes)) != *sha256 {
+            return Err(format!("{} does not match its SHA-256 checksum", self.name));
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TensorSpec {
+    pub name: String,
+    /// `-1` marks a dynamic dimension.
+    pub shape: Vec<i64>,
+}
+
+/// Describes one model version: its files, tensor layout and maximum sequence length.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModelManifest {
+    pub version: String,
+    pub files: Vec<ManifestFile>,
+    pub inputs: Vec<TensorSpec>,
+    pub outputs: Vec<TensorSpec>,
+    pub max_sequence_length: usize,
+}
+
+impl ModelManifest {
+    pub fn builtin(version: &str) -> Result<Self, EmotionPredictorError> {
+        let (_, json) = BUILTIN_MANIFESTS
+            .iter()
+            .find(|(manifest_version, _)| *manifest_version == version)
+            .ok_or_else(|| {
+                EmotionPredictorError::ModelLoading(format!("No built-in manifest for model {}", version))
+            })?;
+
+        let manifest = Self::from_json(json)?;
+        manifest.validate()?;
+
+        Ok(manifest)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, EmotionPredictorError> {
+        serde_json::from_str(json)
+            .map_err(|e| EmotionPredictorError::ModelLoading(format!("Failed to parse model manifest: {}", e)))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, EmotionPredictorError> {
+        let manifest = Self::from_json(&std::fs::read_to_string(path)?)?;
+        manifest.validate()?;
+
+        Ok(manifest)
+    }
+
+    pub fn validate(&self) -> Result<(), EmotionPredictorError> {
+        if self.max_sequence_length == 0 {
+            return Err(EmotionPredictorError::ModelLoading(
+                "max_sequence_length in model manifest must be positive".to_string(),
+            ));
+        }
+
+        for file in &self.files {
+            let safe_name = Path::new(&file.name)
+                .file_name()
+                .is_some_and(|name| name == file.name.as_str());
+
+            if !safe_name {
+                return Err(EmotionPredictorError::ModelLoading(format!(
+                    "Invalid file name in model manifest: {}",
+                    file.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Cheap check used on startup: every file exists with its pinned size.
+    pub fn files_present(&self, model_dir: &Path) -> bool {
+        self.files.iter().all(|file| {
+            std::fs::metadata(model_dir.join(&file.name))
+                .map(|metadata| file.size.is_none_or(|size| metadata.len() == size))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Checks pinned sizes and SHA-256 of every file. Slower than `files_present`, run once per load.
+    pub fn verify_files(&self, model_dir: &Path) -> Result<(), EmotionPredictorError> {
+        for file in &self.files {
+            file.check(&model_dir.join(&file.name))
+                .map_err(EmotionPredictorError::Integrity)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks an in-memory file against its entry. Files the manifest does not list are not checked.
+    pub fn verify_bytes(&self, name: &str, bytes: &[u8]) -> Result<(), EmotionPredictorError> {
+        match self.files.iter().find(|file| file.name == name) {
+            Some(file) => file.check_bytes(bytes).map_err(EmotionPredictorError::Integrity),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the model tensors. A manifest that pins no input or no output tensor fails, since it
+    /// would let any model through.
+    pub fn verify_tensors(&self, inputs: &[TensorSpec], outputs: &[TensorSpec]) -> Result<(), EmotionPredictorError> {
+        if self.inputs.is_empty() || self.outputs.is_empty() {
+            return Err(EmotionPredictorError::Integrity(
+                "Model manifest pins no input or output tensors, so the model cannot be verified".to_string(),
+            ));
+        }
+
+        Self::compare_tensors("input", &self.inputs, inputs)?;
+        Self::compare_tensors("output", &self.outputs, outputs)
+    }
+
+    fn compare_tensors(
+        kind: &str,
+        expected: &[TensorSpec],
+        actual: &[TensorSpec],
+    ) -> Result<(), EmotionPredictorError> {
+        for spec in expected {
+            let found = actual.iter().find(|tensor| tensor.name == spec.name).ok_or_else(|| {
+                EmotionPredictorError::Integrity(format!("Model has no {} tensor '{}'", kind, spec.name))
+            })?;
+
+            let matches = found.shape.len() == spec.shape.len()
+                && spec
+                    .shape
+                    .iter()
+                    .zip(&found.shape)
+                    .all(|(expected_dim, dim)| *expected_dim == -1 || expected_dim == dim);
+
+            if !matches {
+                return Err(EmotionPredictorError::Integrity(format!(
+                    "Model {} tensor '{}' has shape {:?}, manifest expects {:?}",
+                    kind, spec.name, found.shape, spec.shape
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn sha256_file(path: &Path) -> Result<String, EmotionPredictorError> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 64 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(to_hex(&hasher.finalize()))
+}
+
+fn to_hex(hash: &Output<Sha256>) -> String {
+    hash.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sha256_file, ManifestFile, ModelManifest, TensorSpec};
+    use crate::EmotionPredictorError;
+
+    fn tensor(name: &str, shape: &[i64]) -> TensorSpec {
+        TensorSpec {
+            name: name.to_string(),
+            shape: shape.to_vec(),
+        }
+    }
+
+    fn manifest(files: Vec<ManifestFile>) -> ModelManifest {
+        ModelManifest {
+            version: "v0.0.1".to_string(),
+            files,
+            inputs: vec![tensor("input_ids", &[-1, 512]), tensor("attention_mask", &[-1, 512])],
+            outputs: vec![tensor("logits", &[-1, 2])],
+            max_sequence_length: 512,
+        }
+    }
+
+    #[test]
+    fn test_sha256_file() {
+        let path = std::env::temp_dir().join(format!("test_sha256_{}", std::process::id()));
+        std::fs::write(&path, "abc").unwrap();
+
+        let hash = sha256_file(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(hash, "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_builtin_manifest() {
+        let manifest = ModelManifest::builtin("v0.0.1").unwrap();
+
+        assert_eq!(manifest.version, "v0.0.1");
+        assert!(manifest.files.iter().any(|file| file.name == "model.onnx"));
+        assert!(ModelManifest::builtin("v9.9.9").is_err());
+    }
+
+    #[test]
+    fn test_manifest_rejects_paths() {
+        let manifest = manifest(vec![ManifestFile {
+            name: "../model.onnx".to_string(),
+            size: Some(3),
+            sha256: Some("00".to_string()),
+        }]);
+
+        assert!(manifest.validate().is_err());
+    }
+
+    #[test]
+    fn test_verify_files_detects_tampering() {
+        let dir = std::env::temp_dir().join(format!("test_verify_files_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("model.onnx"), "abc").unwrap();
+
+        let manifest = manifest(vec![ManifestFile {
+            name: "model.onnx".to_string(),
+            size: Some(3),
+            sha256: Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()),
+        }]);
+        assert!(manifest.verify_files(&dir).is_ok());
+
+        std::fs::write(dir.join("model.onnx"), "abd").unwrap();
+        let tampered = manifest.verify_files(&dir);
+
+        std::fs::remove_file(dir.join("model.onnx")).unwrap();
+        let missing = manifest.verify_files(&dir);
+        let _ = std::fs::remove_dir_all(&dir);
+
+        assert!(matches!(tampered, Err(EmotionPredictorError::Integrity(msg)) if msg.contains("SHA-256")));
+        assert!(matches!(missing, Err(EmotionPredictorError::Integrity(msg)) if msg.contains("missing")));
+    }
+
+    #[test]
+    fn test_verify_bytes() {
+        let manifest = manifest(vec![ManifestFile {
+            name: "tokenizer.json".to_string(),
+            size: None,
+            sha256: Some("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_string()),
+        }]);
+
+        assert!(manifest.verify_bytes("tokenizer.json", b"abc").is_ok());
+        assert!(ManifestFile {
+            sha256: None,
+            ..manifest.files[0].clone()
+        }
+        .check_bytes(b"abc")
+        .is_err());
+        assert!(manifest.verify_bytes("model.onnx", b"anything").is_ok());
+        assert!(matches!(
+            manifest.verify_bytes("tokenizer.json", b"abd"),
+            Err(EmotionPredictorError::Integrity(_))
+        ));
+    }
+
+    #[test]
+    fn test_verify_tensors() {
+        let manifest = manifest(Vec::new());
+        let inputs = vec![tensor("input_ids", &[-1, 512]), tensor("attention_mask", &[-1, 512])];
+
+        assert!(manifest.verify_tensors(&inputs, &[tensor("logits", &[-1, 2])]).is_ok());
+
+        let wrong_shape = manifest.verify_tensors(&inputs, &[tensor("logits", &[-1, 3])]);
+        assert!(matches!(wrong_shape, Err(EmotionPredictorError::Integrity(msg)) if msg.contains("shape")));
+
+        let missing_input = manifest.verify_tensors(&inputs[..1], &[tensor("logits", &[-1, 2])]);
+        assert!(matches!(missing_input, Err(EmotionPredictorError::Integrity(msg)) if msg.contains("attention_mask")));
+
+        let unpinned = ModelManifest {
+            outputs: Vec::new(),
+            ..manifest
+        };
+        assert!(unpinned.verify_tensors(&inputs, &[tensor("logits", &[-1, 2])]).is_err());
+    }
+}
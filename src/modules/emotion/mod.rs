@@ -1,3 +1,13 @@
+pub mod download;
+pub mod labels;
+pub mod manifest;
+pub mod options;
 pub mod predictor;
+pub mod ranking;
 
+pub use download::ModelDownloader;
+pub use labels::{EmotionLabel, EmotionRegion, LabelingConfig};
+pub use manifest::{ManifestFile, ModelManifest, TensorSpec};
+pub use options::PredictorOptions;
 pub use predictor::{EmotionPrediction, EmotionPredictor, EmotionPredictorError};
+pub use ranking::{rank_by_distance, RankedCandidate};
@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PredictorOptions {
+    /// Directory holding `model.onnx` and `tokenizer.json`. Defaults to `npc_models_cache` next to the executable.
+    pub model_dir: Option<PathBuf>,
+    /// Fail instead of downloading missing models.
+    pub offline: bool,
+    /// Base URL serving the model files. Defaults to Hugging Face.
+    pub mirror_url: Option<String>,
+    pub intra_threads: usize,
+    /// Inputs are padded to the smallest bucket that fits them. Empty pads to the exact length.
+    pub sequence_buckets: Vec<usize>,
+    /// Check files and tensors against a manifest before loading. Only disable for custom models
+    /// you trust.
+    pub verify_integrity: bool,
+    /// Trusted manifest to verify against instead of the built-in one, e.g. for a custom model.
+    pub manifest_path: Option<PathBuf>,
+}
+
+impl Default for PredictorOptions {
+    fn default() -> Self {
+        Self {
+            model_dir: None,
+            offline: false,
+            mirror_url: None,
+            intra_threads: 1,
+            sequence_buckets: vec![32, 64, 128, 256, 512],
+            verify_integrity: true,
+            manifest_path: None,
+        }
+    }
+}
+
+impl PredictorOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.intra_threads == 0 {
+            return Err("intra_threads must be at least 1".to_string());
+        }
+
+        if self.sequence_buckets.first() == Some(&0) || self.sequence_buckets.windows(2).any(|pair| pair[0] >= pair[1])
+        {
+            return Err("sequence_buckets must be positive and strictly increasing".to_string());
+        }
+
+        if let Some(mirror_url) = &self.mirror_url {
+            if !mirror_url.starts_with("http://") && !mirror_url.starts_with("https://") {
+                return Err(format!("mirror_url must be an http(s) URL, got '{}'", mirror_url));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PredictorOptions;
+
+    #[test]
+    fn test_options_from_partial_json() {
+        let options: PredictorOptions =
+            serde_json::from_str(r#"{"model_dir": "/opt/models", "offline": true}"#).unwrap();
+
+        assert_eq!(options.model_dir.unwrap().to_str(), Some("/opt/models"));
+        assert!(options.offline);
+        assert_eq!(options.intra_threads, 1);
+    }
+
+    #[test]
+    fn test_options_validation() {
+        assert!(PredictorOptions::default().validate().is_ok());
+
+        let options = PredictorOptions {
+            intra_threads: 0,
+            ..PredictorOptions::default()
+        };
+        assert!(options.validate().is_err());
+
+        let options = PredictorOptions {
+            sequence_buckets: vec![64, 32],
+            ..PredictorOptions::default()
+        };
+        assert!(options.validate().is_err());
+
+        let options = PredictorOptions {
+            mirror_url: Some("ftp://mirror.local/models".to_string()),
+            ..PredictorOptions::default()
+        };
+        assert!(options.validate().is_err());
+    }
+}
@@ -1,11 +1,12 @@
+use crate::{ModelDownloader, ModelManifest, PredictorOptions, TensorSpec};
 use ndarray::Array2;
 use ort::{
     inputs,
     session::{builder::GraphOptimizationLevel, Session},
-    value::Value,
+    value::{Value, ValueType},
 };
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 use tokenizers::Tokenizer;
 
@@ -31,6 +32,9 @@ pub enum EmotionPredictorError {
 
     #[error("Array shape error: {0}")]
     ArrayShape(String),
+
+    #[error("Model integrity error: {0}")]
+    Integrity(String),
 }
 
 impl From<std::io::Error> for EmotionPredictorError {
@@ -49,65 +53,231 @@ impl From<ort::Error> for EmotionPredictorError {
 pub struct EmotionPrediction {
     pub valence: f32,
     pub arousal: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominance: Option<f32>,
 }
 
 impl EmotionPrediction {
     pub fn new(valence: f32, arousal: f32) -> Self {
-        Self { valence, arousal }
+        Self {
+            valence,
+            arousal,
+            dominance: None,
+        }
+    }
+
+    pub fn with_dominance(mut self, dominance: Option<f32>) -> Self {
+        self.dominance = dominance;
+        self
     }
 
     pub fn values(&self) -> (f32, f32) {
         (self.valence, self.arousal)
     }
+
+    /// Euclidean distance in the circumplex. Dominance only counts when both emotions have it.
+    pub fn distance(&self, other: &EmotionPrediction) -> f32 {
+        let valence_delta = self.valence - other.valence;
+        let arousal_delta = self.arousal - other.arousal;
+        let dominance_delta = match (self.dominance, other.dominance) {
+            (Some(dominance), Some(other_dominance)) => dominance - other_dominance,
+            _ => 0.0,
+        };
+
+        (valence_delta * valence_delta + arousal_delta * arousal_delta + dominance_delta * dominance_delta).sqrt()
+    }
 }
 
 pub struct EmotionPredictor {
     session: Session,
     tokenizer: Tokenizer,
     max_length: usize,
+    sequence_buckets: Vec<usize>,
 }
 
 impl EmotionPredictor {
     const MODEL_VERSION: &'static str = "v0.0.1";
+    const DEFAULT_MAX_LENGTH: usize = 512;
 
     pub fn new() -> Result<Self, EmotionPredictorError> {
-        ort::init().with_name("emotion_prediction").commit()?;
+        Self::with_options(&PredictorOptions::default())
+    }
 
-        let cache_dir = Self::get_cache_directory()?;
-        let model_dir = cache_dir.join(format!("NPC-Prediction-Model-{}", Self::MODEL_VERSION));
+    pub fn with_options(options: &PredictorOptions) -> Result<Self, EmotionPredictorError> {
+        options.validate().map_err(EmotionPredictorError::InvalidInput)?;
+
+        let (model_dir, ready) = match &options.model_dir {
+            Some(model_dir) => (model_dir.clone(), Self::models_present(model_dir)),
+            None => {
+                let model_dir = Self::default_model_dir()?;
+                let ready = Self::models_exist_and_valid(&model_dir);
+                (model_dir, ready)
+            }
+        };
+
+        if !ready {
+            if options.offline {
+                return Err(EmotionPredictorError::ModelLoading(format!(
+                    "Models not found in {} and downloads are disabled",
+                    model_dir.display()
+                )));
+            }
+
+            let base_url = options
+                .mirror_url
+                .as_deref()
+                .unwrap_or(ModelDownloader::DEFAULT_BASE_URL);
+            match options.model_dir {
+                Some(_) => Self::install_models(&model_dir, base_url)?,
+                None => {
+                    Self::check_and_download_models(base_url)?;
+                }
+            }
+        }
 
-        Self::check_and_download_models()?;
+        Self::load_from_dir(&model_dir, options)
+    }
+
+    /// Loads `model.onnx` and `tokenizer.json` from `model_dir` without downloading anything.
+    pub fn from_dir(model_dir: impl AsRef<Path>) -> Result<Self, EmotionPredictorError> {
+        Self::load_from_dir(model_dir.as_ref(), &PredictorOptions::default())
+    }
+
+    /// Builds a predictor from an ONNX model and a `tokenizer.json`, e.g. embedded in the game's assets.
+    pub fn from_bytes(model: &[u8], tokenizer: &[u8]) -> Result<Self, EmotionPredictorError> {
+        Self::from_bytes_with_options(model, tokenizer, &PredictorOptions::default())
+    }
+
+    /// `from_bytes` with inference and integrity options. Download options are ignored.
+    pub fn from_bytes_with_options(
+        model: &[u8],
+        tokenizer: &[u8],
+        options: &PredictorOptions,
+    ) -> Result<Self, EmotionPredictorError> {
+        options.validate().map_err(EmotionPredictorError::InvalidInput)?;
+
+        let manifest = Self::integrity_manifest(options)?;
+        if let Some(manifest) = &manifest {
+            manifest.verify_bytes("model.onnx", model)?;
+            manifest.verify_bytes("tokenizer.json", tokenizer)?;
+        }
+
+        let tokenizer = Tokenizer::from_bytes(tokenizer)
+            .map_err(|e| EmotionPredictorError::Tokenizer(format!("Failed to load tokenizer: {}", e)))?;
+
+        Self::from_parts(model, tokenizer, options, manifest.as_ref())
+    }
+
+    /// The manifest to verify the model against, `None` when verification is explicitly disabled.
+    fn integrity_manifest(options: &PredictorOptions) -> Result<Option<ModelManifest>, EmotionPredictorError> {
+        if !options.verify_integrity {
+            return Ok(None);
+        }
+
+        match &options.manifest_path {
+            Some(path) => ModelManifest::from_file(path).map(Some),
+            None => ModelManifest::builtin(Self::MODEL_VERSION).map(Some),
+        }
+    }
+
+    fn load_from_dir(model_dir: &Path, options: &PredictorOptions) -> Result<Self, EmotionPredictorError> {
+        let onnx_model_path = model_dir.join("model.onnx");
+        if !onnx_model_path.exists() {
+            return Err(EmotionPredictorError::ModelLoading(format!(
+                "Model file not found: {}",
+                onnx_model_path.display()
+            )));
+        }
+
+        let manifest = Self::integrity_manifest(options)?;
+        if let Some(manifest) = &manifest {
+            manifest.verify_files(model_dir)?;
+        }
 
         let tokenizer_path = model_dir.join("tokenizer.json");
         let tokenizer = Self::load_tokenizer_with_fallback(&tokenizer_path)?;
 
-        let onnx_model_path = model_dir.join("model.onnx");
         let model_data = std::fs::read(&onnx_model_path)?;
+
+        Self::from_parts(&model_data, tokenizer, options, manifest.as_ref())
+    }
+
+    fn from_parts(
+        model: &[u8],
+        tokenizer: Tokenizer,
+        options: &PredictorOptions,
+        manifest: Option<&ModelManifest>,
+    ) -> Result<Self, EmotionPredictorError> {
+        ort::init().with_name("emotion_prediction").commit()?;
+
         let session = Session::builder()?
             .with_optimization_level(GraphOptimizationLevel::Level3)?
-            .with_intra_threads(1)?
-            .commit_from_memory(&model_data)?;
+            .with_intra_threads(options.intra_threads)?
+            .commit_from_memory(model)?;
+
+        let mut max_length = match manifest {
+            Some(manifest) => {
+                let inputs: Vec<TensorSpec> = session
+                    .inputs
+                    .iter()
+                    .map(|input| Self::tensor_spec(&input.name, &input.input_type))
+                    .collect();
+                let outputs: Vec<TensorSpec> = session
+                    .outputs
+                    .iter()
+                    .map(|output| Self::tensor_spec(&output.name, &output.output_type))
+                    .collect();
+
+                manifest.verify_tensors(&inputs, &outputs)?;
+                manifest.max_sequence_length
+            }
+            None => Self::DEFAULT_MAX_LENGTH,
+        };
+
+        // Models exported with a fixed sequence length only accept exactly that length.
+        let fixed_length = session
+            .inputs
+            .iter()
+            .find(|input| input.name == "input_ids")
+            .and_then(|input| input.input_type.tensor_shape().and_then(|shape| shape.get(1).copied()))
+            .filter(|&length| length > 0);
+
+        let sequence_buckets = match fixed_length {
+            Some(length) => {
+                max_length = length as usize;
+                vec![max_length]
+            }
+            None => options.sequence_buckets.clone(),
+        };
 
         Ok(Self {
             session,
             tokenizer,
-            max_length: 512,
+            max_length,
+            sequence_buckets,
         })
     }
 
-    fn check_and_download_models() -> Result<String, EmotionPredictorError> {
+    fn tensor_spec(name: &str, value_type: &ValueType) -> TensorSpec {
+        TensorSpec {
+            name: name.to_string(),
+            shape: value_type
+                .tensor_shape()
+                .map(|shape| shape.to_vec())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn check_and_download_models(base_url: &str) -> Result<String, EmotionPredictorError> {
         let cache_dir = Self::get_cache_directory()?;
-        let model_dir = cache_dir.join(format!("NPC-Prediction-Model-{}", Self::MODEL_VERSION));
+        let model_dir = Self::default_model_dir()?;
 
         if Self::models_exist_and_valid(&model_dir) {
             return Ok("Models already up to date".to_string());
         }
 
         Self::cleanup_old_versions(&cache_dir)?;
-        Self::download_models_sync(&model_dir)?;
-
-        let version_file = model_dir.join("version.txt");
-        std::fs::write(&version_file, Self::MODEL_VERSION).map_err(|e| EmotionPredictorError::Io(e.to_string()))?;
+        Self::install_models(&model_dir, base_url)?;
 
         Ok(format!(
             "Models downloaded successfully (version {})",
@@ -115,6 +285,24 @@ impl EmotionPredictor {
         ))
     }
 
+    fn install_models(model_dir: &Path, base_url: &str) -> Result<(), EmotionPredictorError> {
+        eprintln!("NPC Neural Affect Matrix: Downloading models for first-time use...");
+
+        let manifest = ModelManifest::builtin(Self::MODEL_VERSION)?;
+        ModelDownloader::new(base_url).download(&manifest, model_dir)?;
+
+        // Written last so an interrupted install is never mistaken for a complete one.
+        let version_file = model_dir.join("version.txt");
+        std::fs::write(&version_file, Self::MODEL_VERSION).map_err(|e| EmotionPredictorError::Io(e.to_string()))?;
+
+        eprintln!("NPC Neural Affect Matrix: Models downloaded successfully!");
+        Ok(())
+    }
+
+    fn default_model_dir() -> Result<PathBuf, EmotionPredictorError> {
+        Ok(Self::get_cache_directory()?.join(format!("NPC-Prediction-Model-{}", Self::MODEL_VERSION)))
+    }
+
     fn get_cache_directory() -> Result<std::path::PathBuf, EmotionPredictorError> {
         let base_path = std::env::current_exe()
             .or_else(|_| std::env::current_dir())
@@ -138,6 +326,13 @@ impl EmotionPredictor {
             return false;
         }
 
+        Self::models_present(model_dir)
+            && ModelManifest::builtin(Self::MODEL_VERSION)
+                .map(|manifest| manifest.files_present(model_dir))
+                .unwrap_or(false)
+    }
+
+    fn models_present(model_dir: &Path) -> bool {
         model_dir.join("model.onnx").exists()
             && model_dir.join("tokenizer.json").exists()
             && Self::is_placeholder_file(&model_dir.join("model.onnx"))
@@ -163,73 +358,80 @@ impl EmotionPredictor {
         Ok(())
     }
 
-    fn download_models_sync(model_dir: &Path) -> Result<(), EmotionPredictorError> {
-        eprintln!("NPC Neural Affect Matrix: Downloading models for first-time use...");
-
-        std::fs::create_dir_all(model_dir).map_err(|e| EmotionPredictorError::Io(e.to_string()))?;
-
-        let base_url = "https://huggingface.co/Mavdol/NPC-Valence-Arousal-Prediction-ONNX/resolve/main";
-        let files = ["model.onnx", "tokenizer.json", "config.json", "vocab.txt"];
-
-        for file in &files {
-            let url = format!("{}/{}", base_url, file);
-            let file_path = model_dir.join(file);
+    pub fn predict_emotion_from_text(&mut self, text: &str) -> Result<EmotionPrediction, EmotionPredictorError> {
+        let mut predictions = self.predict_batch(&[text])?;
 
-            if file_path.exists() {
-                continue;
-            }
+        Ok(predictions.remove(0))
+    }
 
-            eprintln!("Downloading {}...", file);
+    /// Predicts every text in one inference, padded to the bucket fitting the longest one.
+    pub fn predict_batch(&mut self, texts: &[&str]) -> Result<Vec<EmotionPrediction>, EmotionPredictorError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
 
-            let file_path_str = file_path.to_str().ok_or_else(|| {
-                EmotionPredictorError::InvalidInput(format!(
-                    "Invalid file path for {}: contains non-UTF8 characters",
-                    file
-                ))
-            })?;
+        let encodings = texts
+            .iter()
+            .map(|text| {
+                self.tokenizer
+                    .encode(*text, true)
+                    .map(|encoding| (encoding.get_ids().to_vec(), encoding.get_attention_mask().to_vec()))
+                    .map_err(|e| EmotionPredictorError::Tokenizer(format!("Tokenization error: {}", e)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let longest = encodings
+            .iter()
+            .map(|(token_ids, _)| token_ids.len())
+            .max()
+            .unwrap_or(0);
+        let length = Self::padded_length(&self.sequence_buckets, self.max_length, longest);
+        let (input_ids, attention_mask) = Self::pad_batch(&encodings, length);
+
+        self.run_model(input_ids, attention_mask, texts.len(), length)
+    }
 
-            let response = std::process::Command::new("curl")
-                .args(&["-L", "-o", file_path_str, &url])
-                .output()
-                .map_err(|e| EmotionPredictorError::ModelLoading(format!("Failed to download {}: {}", file, e)))?;
+    /// Smallest bucket holding `token_count` tokens, or `max_length` when none does. Without buckets
+    /// inputs are padded to their actual length.
+    fn padded_length(sequence_buckets: &[usize], max_length: usize, token_count: usize) -> usize {
+        let length = token_count.clamp(1, max_length);
 
-            if !response.status.success() {
-                return Err(EmotionPredictorError::ModelLoading(format!(
-                    "Failed to download {}: {}",
-                    file,
-                    String::from_utf8_lossy(&response.stderr)
-                )));
-            }
+        if sequence_buckets.is_empty() {
+            return length;
         }
 
-        eprintln!("NPC Neural Affect Matrix: Models downloaded successfully!");
-        Ok(())
+        sequence_buckets
+            .iter()
+            .copied()
+            .find(|&bucket| bucket >= length)
+            .map_or(max_length, |bucket| bucket.min(max_length))
     }
 
-    pub fn predict_emotion_from_text(&mut self, text: &str) -> Result<EmotionPrediction, EmotionPredictorError> {
-        let encoding = self
-            .tokenizer
-            .encode(text, true)
-            .map_err(|e| EmotionPredictorError::Tokenizer(format!("Tokenization error: {}", e)))?;
+    /// Flattens token ids and attention masks into `[N, length]` row-major buffers, truncating
+    /// longer encodings and zero-padding shorter ones.
+    fn pad_batch(encodings: &[(Vec<u32>, Vec<u32>)], length: usize) -> (Vec<i64>, Vec<i64>) {
+        let mut input_ids = Vec::with_capacity(encodings.len() * length);
+        let mut attention_mask = Vec::with_capacity(encodings.len() * length);
 
-        let mut token_ids = encoding.get_ids().to_vec();
-        let mut attention_mask = encoding.get_attention_mask().to_vec();
-
-        if token_ids.len() > self.max_length {
-            token_ids.truncate(self.max_length);
-            attention_mask.truncate(self.max_length);
-        } else {
-            while token_ids.len() < self.max_length {
-                token_ids.push(0);
-                attention_mask.push(0);
+        for (token_ids, mask) in encodings {
+            for position in 0..length {
+                input_ids.push(token_ids.get(position).map_or(0, |&id| id as i64));
+                attention_mask.push(mask.get(position).map_or(0, |&value| value as i64));
             }
         }
 
-        let input_ids: Vec<i64> = token_ids.iter().map(|&x| x as i64).collect();
-        let attention_mask: Vec<i64> = attention_mask.iter().map(|&x| x as i64).collect();
+        (input_ids, attention_mask)
+    }
 
-        let input_ids_value = Value::from_array(([1, self.max_length], input_ids))?;
-        let attention_mask_value = Value::from_array(([1, self.max_length], attention_mask))?;
+    fn run_model(
+        &mut self,
+        input_ids: Vec<i64>,
+        attention_mask: Vec<i64>,
+        batch_size: usize,
+        length: usize,
+    ) -> Result<Vec<EmotionPrediction>, EmotionPredictorError> {
+        let input_ids_value = Value::from_array(([batch_size, length], input_ids))?;
+        let attention_mask_value = Value::from_array(([batch_size, length], attention_mask))?;
 
         let outputs = self
             .session
@@ -248,17 +450,32 @@ impl EmotionPredictor {
         let predictions = Array2::from_shape_vec((shape[0] as usize, shape[1] as usize), data.to_vec())
             .map_err(|e| EmotionPredictorError::ArrayShape(format!("Failed to create predictions array: {}", e)))?;
 
-        if predictions.shape() != &[1, 2] {
+        Self::emotions_from_output(&predictions, batch_size)
+    }
+
+    fn emotions_from_output(
+        predictions: &Array2<f32>,
+        batch_size: usize,
+    ) -> Result<Vec<EmotionPrediction>, EmotionPredictorError> {
+        if predictions.shape() != [batch_size, 2] && predictions.shape() != [batch_size, 3] {
             return Err(EmotionPredictorError::Inference(format!(
-                "Unexpected output shape: {:?}, expected [1, 2]",
-                predictions.shape()
+                "Unexpected output shape: {:?}, expected [{}, 2] or [{}, 3]",
+                predictions.shape(),
+                batch_size,
+                batch_size
             )));
         }
 
-        let valence = (predictions[[0, 0]] * 100.0).round() / 100.0;
-        let arousal = (predictions[[0, 1]] * 100.0).round() / 100.0;
+        let round = |value: f32| (value * 100.0).round() / 100.0;
 
-        Ok(EmotionPrediction::new(valence, arousal))
+        Ok(predictions
+            .rows()
+            .into_iter()
+            .map(|row| {
+                let dominance = (row.len() == 3).then(|| round(row[2]));
+                EmotionPrediction::new(round(row[0]), round(row[1])).with_dominance(dominance)
+            })
+            .collect())
     }
 
     fn load_tokenizer_with_fallback(tokenizer_path: &Path) -> Result<Tokenizer, EmotionPredictorError> {
@@ -302,7 +519,8 @@ impl EmotionPredictor {
 
 #[cfg(test)]
 mod tests {
-    use super::{EmotionPrediction, EmotionPredictor, EmotionPredictorError};
+    use super::{EmotionPrediction, EmotionPredictor, EmotionPredictorError, ModelDownloader, PredictorOptions};
+    use ndarray::Array2;
     use std::path::Path;
 
     #[test]
@@ -338,6 +556,18 @@ mod tests {
         assert_eq!(prediction.arousal, -0.32);
     }
 
+    #[test]
+    fn test_emotion_prediction_dominance_serialization() {
+        let prediction = EmotionPrediction::new(0.1, 0.2);
+        assert_eq!(prediction.dominance, None);
+        assert!(!serde_json::to_string(&prediction).unwrap().contains("dominance"));
+
+        let prediction = prediction.with_dominance(Some(-0.4));
+        let json = serde_json::to_string(&prediction).unwrap();
+        let parsed: EmotionPrediction = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.dominance, Some(-0.4));
+    }
+
     #[test]
     fn test_emotion_prediction_values() {
         let prediction = EmotionPrediction::new(0.72, -0.20);
@@ -359,7 +589,7 @@ mod tests {
 
     #[test]
     fn test_check_and_download_models() {
-        let result = EmotionPredictor::check_and_download_models();
+        let result = EmotionPredictor::check_and_download_models(ModelDownloader::DEFAULT_BASE_URL);
 
         match result {
             Ok(message) => {
@@ -374,6 +604,182 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_offline_without_models_fails() {
+        let temp_dir = std::env::temp_dir().join(format!("test_offline_models_{}", std::process::id()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+
+        let options = PredictorOptions {
+            model_dir: Some(temp_dir.clone()),
+            offline: true,
+            ..PredictorOptions::default()
+        };
+        let result = EmotionPredictor::with_options(&options);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        match result {
+            Err(EmotionPredictorError::ModelLoading(msg)) => assert!(msg.contains("downloads are disabled")),
+            _ => panic!("Expected ModelLoading error"),
+        }
+    }
+
+    #[test]
+    fn test_from_dir_missing_model() {
+        let result = EmotionPredictor::from_dir("/nonexistent/models");
+
+        assert!(matches!(result, Err(EmotionPredictorError::ModelLoading(_))));
+    }
+
+    #[test]
+    fn test_tampered_model_is_rejected() {
+        let temp_dir = std::env::temp_dir().join(format!("test_tampered_models_{}", std::process::id()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        std::fs::write(temp_dir.join("model.onnx"), vec![1u8; 2000]).unwrap();
+        std::fs::write(temp_dir.join("tokenizer.json"), "{}").unwrap();
+
+        let manifest_path = temp_dir.with_extension("json");
+        std::fs::write(
+            &manifest_path,
+            r#"{"version": "v0.0.1", "files": [{"name": "model.onnx", "size": 2000, "sha256": "00"}],
+                "inputs": [], "outputs": [], "max_sequence_length": 512}"#,
+        )
+        .unwrap();
+
+        let options = PredictorOptions {
+            model_dir: Some(temp_dir.clone()),
+            offline: true,
+            manifest_path: Some(manifest_path.clone()),
+            ..PredictorOptions::default()
+        };
+        let from_dir = EmotionPredictor::with_options(&options);
+        let from_bytes = EmotionPredictor::from_bytes_with_options(&[1u8; 2000], b"{}", &options);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        let _ = std::fs::remove_file(&manifest_path);
+
+        assert!(matches!(from_dir, Err(EmotionPredictorError::Integrity(_))));
+        assert!(matches!(from_bytes, Err(EmotionPredictorError::Integrity(_))));
+    }
+
+    #[test]
+    fn test_corrupted_model_is_rejected_by_builtin_manifest() {
+        let temp_dir = std::env::temp_dir().join(format!("test_corrupted_models_{}", std::process::id()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        std::fs::write(temp_dir.join("model.onnx"), vec![0u8; 2000]).unwrap();
+        std::fs::write(temp_dir.join("tokenizer.json"), "{}").unwrap();
+
+        let options = PredictorOptions {
+            model_dir: Some(temp_dir.clone()),
+            offline: true,
+            ..PredictorOptions::default()
+        };
+        let from_dir = EmotionPredictor::with_options(&options);
+        let from_bytes = EmotionPredictor::from_bytes_with_options(&[0u8; 2000], b"{}", &options);
+
+        let _ = std::fs::remove_dir_all(&temp_dir);
+
+        assert!(matches!(from_dir, Err(EmotionPredictorError::Integrity(_))));
+        assert!(matches!(from_bytes, Err(EmotionPredictorError::Integrity(_))));
+    }
+
+    #[test]
+    fn test_integrity_manifest_opt_out() {
+        let options = PredictorOptions {
+            verify_integrity: false,
+            ..PredictorOptions::default()
+        };
+
+        assert!(EmotionPredictor::integrity_manifest(&options).unwrap().is_none());
+        assert!(EmotionPredictor::integrity_manifest(&PredictorOptions::default())
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn test_from_bytes_invalid_tokenizer() {
+        let options = PredictorOptions {
+            verify_integrity: false,
+            ..PredictorOptions::default()
+        };
+        let result = EmotionPredictor::from_bytes_with_options(b"model", b"invalid json content", &options);
+
+        assert!(matches!(result, Err(EmotionPredictorError::Tokenizer(_))));
+    }
+
+    #[test]
+    fn test_pad_batch() {
+        let encodings = vec![(vec![101, 7, 102], vec![1, 1, 1]), (vec![101, 102], vec![1, 1])];
+
+        let (input_ids, attention_mask) = EmotionPredictor::pad_batch(&encodings, 4);
+
+        assert_eq!(input_ids, vec![101, 7, 102, 0, 101, 102, 0, 0]);
+        assert_eq!(attention_mask, vec![1, 1, 1, 0, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_pad_batch_truncates() {
+        let encodings = vec![(vec![1, 2, 3, 4, 5], vec![1; 5])];
+
+        let (input_ids, _) = EmotionPredictor::pad_batch(&encodings, 4);
+
+        assert_eq!(input_ids, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_padded_length_buckets() {
+        let buckets = [32, 64, 128, 256, 512];
+
+        assert_eq!(EmotionPredictor::padded_length(&buckets, 512, 7), 32);
+        assert_eq!(EmotionPredictor::padded_length(&buckets, 512, 32), 32);
+        assert_eq!(EmotionPredictor::padded_length(&buckets, 512, 33), 64);
+        assert_eq!(EmotionPredictor::padded_length(&buckets, 512, 900), 512);
+        assert_eq!(EmotionPredictor::padded_length(&buckets, 128, 200), 128);
+        assert_eq!(EmotionPredictor::padded_length(&[32, 64], 512, 100), 512);
+    }
+
+    #[test]
+    fn test_padded_length_without_buckets() {
+        assert_eq!(EmotionPredictor::padded_length(&[], 512, 7), 7);
+        assert_eq!(EmotionPredictor::padded_length(&[], 512, 0), 1);
+        assert_eq!(EmotionPredictor::padded_length(&[], 512, 900), 512);
+    }
+
+    #[test]
+    fn test_short_dialogue_uses_smallest_bucket() {
+        let buckets = PredictorOptions::default().sequence_buckets;
+        let encodings = vec![
+            (vec![101, 7592, 102], vec![1, 1, 1]),
+            (vec![101, 2131, 2041, 999, 102], vec![1; 5]),
+        ];
+
+        let longest = encodings.iter().map(|(token_ids, _)| token_ids.len()).max().unwrap();
+        let length = EmotionPredictor::padded_length(&buckets, 512, longest);
+        let (input_ids, attention_mask) = EmotionPredictor::pad_batch(&encodings, length);
+
+        assert_eq!(length, 32);
+        assert_eq!(input_ids.len(), 2 * 32);
+        assert_eq!(attention_mask.len(), 2 * 32);
+        assert_eq!(&input_ids[32..37], &[101, 2131, 2041, 999, 102]);
+        assert_eq!(attention_mask[..32].iter().sum::<i64>(), 3);
+        assert_eq!(attention_mask[32..].iter().sum::<i64>(), 5);
+    }
+
+    #[test]
+    fn test_emotions_from_output() {
+        let output = Array2::from_shape_vec((2, 2), vec![0.514, -0.2, -0.7, 0.333]).unwrap();
+
+        let emotions = EmotionPredictor::emotions_from_output(&output, 2).unwrap();
+
+        assert_eq!(emotions.len(), 2);
+        assert_eq!((emotions[0].valence, emotions[0].arousal), (0.51, -0.2));
+        assert_eq!((emotions[1].valence, emotions[1].arousal), (-0.7, 0.33));
+        assert!(emotions[1].dominance.is_none());
+
+        let result = EmotionPredictor::emotions_from_output(&output, 3);
+        assert!(matches!(result, Err(EmotionPredictorError::Inference(_))));
+    }
+
     #[test]
     fn test_load_tokenizer_with_fallback_invalid_file() {
         let temp_dir = std::env::temp_dir().join(format!("test_invalid_tokenizer_{}", std::process::id()));
@@ -0,0 +1,61 @@
+use crate::EmotionPrediction;
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankedCandidate {
+    /// Position of the candidate in the input.
+    pub index: usize,
+    pub distance: f32,
+}
+
+/// Candidates ordered from the closest to the farthest from `target`. Ties keep input order.
+pub fn rank_by_distance(candidates: &[EmotionPrediction], target: &EmotionPrediction) -> Vec<RankedCandidate> {
+    let mut ranked: Vec<RankedCandidate> = candidates
+        .iter()
+        .enumerate()
+        .map(|(index, candidate)| RankedCandidate {
+            index,
+            distance: candidate.distance(target),
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rank_by_distance;
+    use crate::EmotionPrediction;
+
+    #[test]
+    fn test_rank_by_distance() {
+        let candidates = vec![
+            EmotionPrediction::new(0.8, 0.5),
+            EmotionPrediction::new(-0.7, 0.6),
+            EmotionPrediction::new(-0.2, -0.3),
+        ];
+
+        let ranked = rank_by_distance(&candidates, &EmotionPrediction::new(-0.8, 0.7));
+
+        let order: Vec<usize> = ranked.iter().map(|candidate| candidate.index).collect();
+        assert_eq!(order, vec![1, 2, 0]);
+        assert!((ranked[0].distance - 0.02_f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dominance_counts_when_both_have_it() {
+        let target = EmotionPrediction::new(0.0, 0.0).with_dominance(Some(0.8));
+        let candidates = vec![
+            EmotionPrediction::new(0.0, 0.0).with_dominance(Some(-0.8)),
+            EmotionPrediction::new(0.1, 0.0).with_dominance(Some(0.8)),
+            EmotionPrediction::new(0.0, 0.0),
+        ];
+
+        let ranked = rank_by_distance(&candidates, &target);
+
+        assert_eq!(ranked[0].index, 2);
+        assert_eq!(ranked[2].index, 0);
+    }
+}
@@ -1,7 +1,11 @@
-use crate::{EmotionPrediction, NpcConfig};
+use crate::config::DecayCurve;
+use crate::modules::emotion::{rank_by_distance, RankedCandidate};
 use crate::EmotionPredictorError;
-use crate::{MemoryRecord, MemoryStore};
-use std::f32::consts::E;
+use crate::{AffectState, AffectStore, EmotionLabel, EmotionPrediction, GameEvent, GameTime, Homeostasis, NpcConfig};
+use crate::{
+    AppliedBlendWeights, EmotionEvent, EvaluationBreakdown, MemoryContribution, MemoryRecord, MemoryStore,
+    SourceRegistry, TriggerStore,
+};
 use uuid::Uuid;
 
 #[derive(Clone)]
@@ -37,35 +41,296 @@ impl MemoryEmotionEvaluator {
         &self,
         text: &str,
         predicted_emotion: &EmotionPrediction,
-        past_time: i64,
+        occurred_at: GameTime,
         source_id: Option<&str>,
     ) -> Result<EmotionPrediction, EmotionPredictorError> {
-        let global_emotion = self.calculate_current_emotion()?;
+        Ok(self
+            .evaluate_with_breakdown(text, predicted_emotion, occurred_at, source_id, None)?
+            .final_emotion)
+    }
+
+    /// Same as `evaluate_npc_emotion`, returning every step of the evaluation with the `top_memories`
+    /// memories that weighed the most.
+    pub fn explain_npc_emotion(
+        &self,
+        text: &str,
+        predicted_emotion: &EmotionPrediction,
+        occurred_at: GameTime,
+        source_id: Option<&str>,
+        top_memories: usize,
+    ) -> Result<EvaluationBreakdown, EmotionPredictorError> {
+        self.evaluate_with_breakdown(text, predicted_emotion, occurred_at, source_id, Some(top_memories))
+    }
+
+    /// How the NPC would react to an interaction, without storing a memory or changing its mood.
+    pub fn preview_npc_emotion(
+        &self,
+        predicted_emotion: &EmotionPrediction,
+        source_id: Option<&str>,
+    ) -> Result<EmotionPrediction, EmotionPredictorError> {
+        Ok(self.breakdown(predicted_emotion, source_id, None)?.final_emotion)
+    }
+
+    /// `preview_npc_emotion` for several candidate interactions with the same source, in order.
+    pub fn preview_npc_emotions(
+        &self,
+        predicted_emotions: &[EmotionPrediction],
+        source_id: Option<&str>,
+    ) -> Result<Vec<EmotionPrediction>, EmotionPredictorError> {
+        let global_emotion = self.current_affect_state()?.mood;
+        let source_emotion = match source_id.or(self.source_id.as_deref()) {
+            Some(source_id) => Some(self.calculate_current_emotion_towards_source(source_id)?),
+            None => None,
+        };
+
+        Ok(predicted_emotions
+            .iter()
+            .map(|predicted_emotion| {
+                self.combine_emotions_psychologically(predicted_emotion, source_emotion.as_ref(), &global_emotion)
+            })
+            .collect())
+    }
+
+    /// Ranks candidate lines by how close their emotion is to `target`, or by default to the NPC's
+    /// current emotion towards `source_id` (its overall emotion without a source).
+    pub fn rank_candidates(
+        &self,
+        predicted_emotions: &[EmotionPrediction],
+        target: Option<&EmotionPrediction>,
+        source_id: Option<&str>,
+    ) -> Result<Vec<RankedCandidate>, EmotionPredictorError> {
+        let target = match (target, source_id.or(self.source_id.as_deref())) {
+            (Some(target), _) => target.clone(),
+            (None, Some(source_id)) => self.calculate_current_emotion_towards_source(source_id)?,
+            (None, None) => self.calculate_current_emotion()?,
+        };
+
+        Ok(rank_by_distance(predicted_emotions, &target))
+    }
+
+    fn evaluate_with_breakdown(
+        &self,
+        text: &str,
+        predicted_emotion: &EmotionPrediction,
+        occurred_at: GameTime,
+        source_id: Option<&str>,
+        top_memories: Option<usize>,
+    ) -> Result<EvaluationBreakdown, EmotionPredictorError> {
+        let breakdown = self.breakdown(predicted_emotion, source_id, top_memories)?;
 
-        let source_emotion = if let Some(source_id) = source_id.or(self.source_id.as_deref()) {
+        self.store_emotion_in_memory(text, &breakdown.final_emotion, occurred_at, source_id)?;
+
+        let mut affect_state = self.current_affect_state()?;
+        affect_state.apply_interaction(&self.config.mood, &breakdown.final_emotion);
+        AffectStore::set(&self.npc_id, affect_state)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to store affect state: {}", e)))?;
+
+        self.check_triggers()?;
+
+        Ok(breakdown)
+    }
+
+    /// Runs the blending pipeline without writing anything.
+    fn breakdown(
+        &self,
+        predicted_emotion: &EmotionPrediction,
+        source_id: Option<&str>,
+        top_memories: Option<usize>,
+    ) -> Result<EvaluationBreakdown, EmotionPredictorError> {
+        let global_emotion = self.current_affect_state()?.mood;
+        let effective_source_id = source_id.or(self.source_id.as_deref());
+
+        let source_emotion = if let Some(source_id) = effective_source_id {
             Some(self.calculate_current_emotion_towards_source(source_id)?)
         } else {
             None
         };
 
+        let memories = match top_memories {
+            Some(count) => {
+                let records = match effective_source_id {
+                    Some(source_id) => self.source_records(source_id)?,
+                    None => MemoryStore::get_all(&self.npc_id)
+                        .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get memory records: {}", e)))?
+                        .into_iter()
+                        .map(|record| (record, 1.0))
+                        .collect(),
+                };
+                self.top_contributions(&records, self.current_time()?, count)
+            }
+            None => Vec::new(),
+        };
+
+        let overall_emotion = match source_emotion {
+            Some(_) => None,
+            None => Some(self.calculate_current_emotion()?),
+        };
+
         let final_emotion =
             self.combine_emotions_psychologically(predicted_emotion, source_emotion.as_ref(), &global_emotion);
 
-        self.store_emotion_in_memory(text, &final_emotion, past_time, source_id)?;
+        Ok(EvaluationBreakdown {
+            predicted: predicted_emotion.clone(),
+            global: global_emotion,
+            weights: self.applied_blend_weights(source_emotion.is_some()),
+            source: source_emotion,
+            overall: overall_emotion,
+            final_emotion,
+            memories,
+        })
+    }
+
+    /// Evaluates the NPC's triggers and queues an event for each one that fired or cleared since the last check.
+    pub fn check_triggers(&self) -> Result<Vec<EmotionEvent>, EmotionPredictorError> {
+        let triggers = TriggerStore::get_all(&self.npc_id).map_err(EmotionPredictorError::Inference)?;
+        if triggers.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let now = self.current_time()?;
+        let overall_emotion = self.calculate_current_emotion()?;
+        let mut events = Vec::new();
+
+        for trigger in triggers {
+            let emotion = match &trigger.source_id {
+                Some(source_id) => self.calculate_current_emotion_towards_source(source_id)?,
+                None => overall_emotion.clone(),
+            };
+            let label = self.label_emotion(&emotion);
+            let matched = trigger.condition.matches(&emotion, &label);
+
+            let Some(kind) =
+                TriggerStore::update(&self.npc_id, &trigger.id, matched).map_err(EmotionPredictorError::Inference)?
+            else {
+                continue;
+            };
+
+            let event = EmotionEvent {
+                npc_id: self.npc_id.clone(),
+                trigger_id: trigger.id,
+                kind,
+                source_id: trigger.source_id,
+                emotion,
+                label: label.primary,
+                game_time: now,
+            };
+            TriggerStore::push_event(event.clone()).map_err(EmotionPredictorError::Inference)?;
+            events.push(event);
+        }
+
+        Ok(events)
+    }
+
+    /// Appraises a game event through the configured event table and evaluates it like an interaction.
+    pub fn evaluate_npc_event(
+        &self,
+        event: &GameEvent,
+        occurred_at: GameTime,
+    ) -> Result<EmotionPrediction, EmotionPredictorError> {
+        let mut appraised_emotion = self
+            .config
+            .events
+            .appraise(event, self.config.personality.dominance.is_some())
+            .map_err(EmotionPredictorError::Inference)?;
+
+        if let Some(target_id) = event.target_id.as_deref().filter(|target_id| *target_id != self.npc_id) {
+            appraised_emotion = self
+                .config
+                .observation
+                .vicarious_emotion(&appraised_emotion, self.affinity(target_id)?);
+        }
+
+        self.evaluate_npc_emotion(
+            &event.describe(),
+            &appraised_emotion,
+            occurred_at,
+            event.source_id.as_deref(),
+        )
+    }
+
+    /// Evaluates an interaction the NPC witnessed between `source_id` and `target_id`. The memory is
+    /// stored about `source_id`, and the text emotion is felt through the NPC's feelings for `target_id`.
+    pub fn observe_npc_interaction(
+        &self,
+        text: &str,
+        predicted_emotion: &EmotionPrediction,
+        occurred_at: GameTime,
+        source_id: &str,
+        target_id: &str,
+    ) -> Result<EmotionPrediction, EmotionPredictorError> {
+        let observed_emotion = self
+            .config
+            .observation
+            .vicarious_emotion(predicted_emotion, self.affinity(target_id)?);
 
-        Ok(final_emotion)
+        self.evaluate_npc_emotion(
+            &format!("[observed] {} -> {}: {}", source_id, target_id, text),
+            &observed_emotion,
+            occurred_at,
+            Some(source_id),
+        )
     }
 
+    /// How much the NPC likes `target_id` compared to its baseline valence.
+    fn affinity(&self, target_id: &str) -> Result<f32, EmotionPredictorError> {
+        let target_emotion = self.calculate_current_emotion_towards_source(target_id)?;
+        Ok(target_emotion.valence - self.config.personality.valence)
+    }
+
+    pub fn current_affect_state(&self) -> Result<AffectState, EmotionPredictorError> {
+        let now = self.current_time()?;
+
+        AffectStore::get(&self.npc_id, &self.config.mood, &self.config.personality, now)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get affect state: {}", e)))
+    }
+
+    /// Emotion towards a source or any of its aliases. For a faction, memories about its members
+    /// are included with the configured spillover weight. Relaxes towards the baseline since the
+    /// last memory involving the source.
     pub fn calculate_current_emotion_towards_source(
         &self,
         source_id: &str,
     ) -> Result<EmotionPrediction, EmotionPredictorError> {
-        let records = MemoryStore::get_by_source(&self.npc_id, source_id)
-            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get memory records: {}", e)))?;
+        let records = self.source_records(source_id)?;
+        let now = self.current_time()?;
+
+        let weighted_emotion = self.calculate_scaled_weighted_emotion(&records, now);
+        let last_interaction = records
+            .iter()
+            .map(|(record, _)| record.occurred_at)
+            .max()
+            .unwrap_or(now);
 
-        let (valence, arousal) = self.calculate_weighted_emotion(&records);
+        Ok(Homeostasis::relax(
+            &weighted_emotion,
+            &self.config.personality,
+            &self.config.homeostasis,
+            now - last_interaction,
+        ))
+    }
+
+    /// Memories about a source with their scale: 1.0, or the faction spillover for memories about members.
+    fn source_records(&self, source_id: &str) -> Result<Vec<(MemoryRecord, f32)>, EmotionPredictorError> {
+        let source_id = SourceRegistry::resolve(source_id).map_err(EmotionPredictorError::Inference)?;
+        let mut records: Vec<(MemoryRecord, f32)> = self
+            .memories_about(&source_id)?
+            .into_iter()
+            .map(|record| (record, 1.0))
+            .collect();
+
+        let spillover = self.config.factions.spillover;
+        if spillover > 0.0 {
+            let members = SourceRegistry::members(&source_id).map_err(EmotionPredictorError::Inference)?;
+            for member in members {
+                records.extend(
+                    self.memories_about(&member)?
+                        .into_iter()
+                        .map(|record| (record, spillover)),
+                );
+            }
+        }
 
-        Ok(EmotionPrediction::new(valence, arousal))
+        Ok(records)
     }
 
     pub fn calculate_current_emotion(&self) -> Result<EmotionPrediction, EmotionPredictorError> {
@@ -73,51 +338,208 @@ impl MemoryEmotionEvaluator {
             .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get memory records: {}", e)))?;
 
         if records.is_empty() {
-            return Ok(EmotionPrediction::new(
-                self.config.personality.valence,
-                self.config.personality.arousal,
-            ));
+            return Ok(
+                EmotionPrediction::new(self.config.personality.valence, self.config.personality.arousal)
+                    .with_dominance(self.config.personality.dominance),
+            );
         }
 
-        let (valence, arousal) = self.calculate_weighted_emotion(&records);
+        let now = self.current_time()?;
+        let weighted_emotion = self.calculate_weighted_emotion(&records, now);
+
+        let last_interaction = records.iter().map(|record| record.occurred_at).max().unwrap_or(now);
+
+        Ok(Homeostasis::relax(
+            &weighted_emotion,
+            &self.config.personality,
+            &self.config.homeostasis,
+            now - last_interaction,
+        ))
+    }
+
+    /// Game minutes until the current emotion is back to the personality baseline if nothing else happens.
+    pub fn time_to_baseline(&self) -> Result<Option<GameTime>, EmotionPredictorError> {
+        let emotion = self.calculate_current_emotion()?;
+
+        Ok(Homeostasis::time_to_baseline(
+            &emotion,
+            &self.config.personality,
+            &self.config.homeostasis,
+        ))
+    }
+
+    pub fn label_emotion(&self, emotion: &EmotionPrediction) -> EmotionLabel {
+        self.config.labels.label(emotion)
+    }
+
+    pub(crate) fn current_time(&self) -> Result<GameTime, EmotionPredictorError> {
+        MemoryStore::now(&self.npc_id)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get game time: {}", e)))
+    }
+
+    /// Memories about a source, stored under its canonical id or any of its aliases.
+    pub fn memories_about(&self, source_id: &str) -> Result<Vec<MemoryRecord>, EmotionPredictorError> {
+        let identities = SourceRegistry::identities(source_id).map_err(EmotionPredictorError::Inference)?;
 
-        Ok(EmotionPrediction::new(valence, arousal))
+        MemoryStore::get_by_sources(&self.npc_id, &identities)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get memory records: {}", e)))
     }
 
-    fn calculate_weighted_emotion(&self, records: &[MemoryRecord]) -> (f32, f32) {
-        let decay_rate = self.config.memory.decay_rate;
+    fn calculate_weighted_emotion(&self, records: &[MemoryRecord], now: GameTime) -> EmotionPrediction {
+        let records: Vec<(MemoryRecord, f32)> = records.iter().map(|record| (record.clone(), 1.0)).collect();
+
+        self.calculate_scaled_weighted_emotion(&records, now)
+    }
+
+    /// Weighted emotion where each record's deviation from the baseline is multiplied by its scale.
+    /// The rest of a scaled record's weight stays on the baseline, so a low scale weakens it even
+    /// when there are no unscaled records.
+    fn calculate_scaled_weighted_emotion(&self, records: &[(MemoryRecord, f32)], now: GameTime) -> EmotionPrediction {
+        let decay_curve = self.config.memory.curve();
 
         let personality_valence = self.config.personality.valence;
         let personality_arousal = self.config.personality.arousal;
+        let amplification = 1.0 + self.config.personality.volatility;
 
         let mut weighted_valence = 0.0;
         let mut weighted_arousal = 0.0;
+        let mut weighted_dominance = 0.0;
         let mut total_weight = 0.0;
+        let mut total_dominance_weight = 0.0;
 
-        for record in records {
-            let weight = E.powf(-decay_rate * record.past_time as f32);
+        for (record, scale) in records {
+            let full_weight = self.memory_weight(record, 1.0, now, &decay_curve);
+            let weight = full_weight * scale;
 
             let valence_deviation = record.valence - personality_valence;
             let arousal_deviation = record.arousal - personality_arousal;
 
             weighted_valence += valence_deviation * weight;
             weighted_arousal += arousal_deviation * weight;
-            total_weight += weight;
+            total_weight += full_weight;
+
+            if let (Some(personality_dominance), Some(dominance)) =
+                (self.config.personality.dominance, record.dominance)
+            {
+                weighted_dominance += (dominance - personality_dominance) * weight;
+                total_dominance_weight += full_weight;
+            }
         }
 
         let final_valence = if total_weight > 0.0 {
-            ((weighted_valence / total_weight) + personality_valence).clamp(-1.0, 1.0)
+            ((weighted_valence / total_weight) * amplification + personality_valence).clamp(-1.0, 1.0)
         } else {
             personality_valence
         };
 
         let final_arousal = if total_weight > 0.0 {
-            ((weighted_arousal / total_weight) + personality_arousal).clamp(-1.0, 1.0)
+            ((weighted_arousal / total_weight) * amplification + personality_arousal).clamp(-1.0, 1.0)
         } else {
             personality_arousal
         };
 
-        (final_valence, final_arousal)
+        let final_dominance = self.config.personality.dominance.map(|personality_dominance| {
+            if total_dominance_weight > 0.0 {
+                ((weighted_dominance / total_dominance_weight) * amplification + personality_dominance).clamp(-1.0, 1.0)
+            } else {
+                personality_dominance
+            }
+        });
+
+        EmotionPrediction::new(final_valence, final_arousal).with_dominance(final_dominance)
+    }
+
+    /// Decay weight of a memory times its scale, with the negativity bias applied to unpleasant memories.
+    fn memory_weight(&self, record: &MemoryRecord, scale: f32, now: GameTime, decay_curve: &DecayCurve) -> f32 {
+        let weight = decay_curve.weight(record.age(now)) * scale;
+
+        if record.valence < self.config.personality.valence {
+            weight * (1.0 + self.config.personality.negativity_bias)
+        } else {
+            weight
+        }
+    }
+
+    fn top_contributions(
+        &self,
+        records: &[(MemoryRecord, f32)],
+        now: GameTime,
+        count: usize,
+    ) -> Vec<MemoryContribution> {
+        let decay_curve = self.config.memory.curve();
+        let weights: Vec<f32> = records
+            .iter()
+            .map(|(record, scale)| self.memory_weight(record, *scale, now, &decay_curve))
+            .collect();
+        let total_weight: f32 = weights.iter().sum();
+
+        let mut contributions: Vec<MemoryContribution> = records
+            .iter()
+            .zip(weights)
+            .map(|((record, _), weight)| MemoryContribution {
+                id: record.id.clone(),
+                source_id: record.source_id.clone(),
+                content: record.content.clone(),
+                valence: record.valence,
+                arousal: record.arousal,
+                dominance: record.dominance,
+                age: record.age(now),
+                decay_weight: decay_curve.weight(record.age(now)),
+                share: if total_weight > 0.0 { weight / total_weight } else { 0.0 },
+            })
+            .collect();
+
+        contributions.sort_by(|a, b| b.share.total_cmp(&a.share));
+        contributions.truncate(count);
+
+        contributions
+    }
+
+    /// Dominance of the text, falling back to the configured value when the model has no dominance output.
+    fn resolve_text_dominance(&self, text_emotion: &EmotionPrediction) -> Option<f32> {
+        let personality_dominance = self.config.personality.dominance?;
+
+        Some(
+            text_emotion
+                .dominance
+                .or(self.config.dominance.fallback)
+                .unwrap_or(personality_dominance),
+        )
+    }
+
+    /// Blending weights scaled by reactivity and normalized to sum to 1.0. Everything goes to the mood
+    /// when no input has any weight.
+    fn applied_blend_weights(&self, has_source: bool) -> AppliedBlendWeights {
+        let blending = &self.config.blending;
+        let personality = &self.config.personality;
+
+        let (source_weight, text_weight, global_weight) = if has_source {
+            (
+                blending.with_source.source,
+                blending.with_source.text,
+                blending.with_source.global,
+            )
+        } else {
+            (0.0, blending.without_source.text, blending.without_source.global)
+        };
+        let text_weight = text_weight * personality.reactivity;
+        let total_weight = source_weight + text_weight + global_weight;
+
+        if total_weight <= 0.0 {
+            return AppliedBlendWeights {
+                source: 0.0,
+                text: 0.0,
+                global: 1.0,
+                inertia: personality.inertia,
+            };
+        }
+
+        AppliedBlendWeights {
+            source: source_weight / total_weight,
+            text: text_weight / total_weight,
+            global: global_weight / total_weight,
+            inertia: personality.inertia,
+        }
     }
 
     fn combine_emotions_psychologically(
@@ -126,32 +548,59 @@ impl MemoryEmotionEvaluator {
         source_emotion: Option<&EmotionPrediction>,
         global_emotion: &EmotionPrediction,
     ) -> EmotionPrediction {
-        let (final_valence, final_arousal) = if let Some(source_emotion) = source_emotion {
-            let valence =
-                (source_emotion.valence * 0.5) + (text_emotion.valence * 0.35) + (global_emotion.valence * 0.15);
+        let personality = &self.config.personality;
+        let text_dominance = self.resolve_text_dominance(text_emotion);
+        let weights = self.applied_blend_weights(source_emotion.is_some());
 
-            let arousal =
-                (source_emotion.arousal * 0.5) + (text_emotion.arousal * 0.35) + (global_emotion.arousal * 0.15);
+        let blend = |source: f32, text: f32, global: f32| {
+            let blended = source * weights.source + text * weights.text + global * weights.global;
 
-            (valence, arousal)
-        } else {
-            let valence = (text_emotion.valence * 0.7) + (global_emotion.valence * 0.3);
-            let arousal = (text_emotion.arousal * 0.7) + (global_emotion.arousal * 0.3);
+            (global + (blended - global) * (1.0 - weights.inertia)).clamp(-1.0, 1.0)
+        };
 
-            (valence, arousal)
+        let text_valence = if text_emotion.valence < personality.valence {
+            personality.valence + (text_emotion.valence - personality.valence) * (1.0 + personality.negativity_bias)
+        } else {
+            text_emotion.valence
         };
 
-        EmotionPrediction::new(final_valence.clamp(-1.0, 1.0), final_arousal.clamp(-1.0, 1.0))
+        let valence = blend(
+            source_emotion.map_or(global_emotion.valence, |source| source.valence),
+            text_valence,
+            global_emotion.valence,
+        );
+
+        let arousal = blend(
+            source_emotion.map_or(global_emotion.arousal, |source| source.arousal),
+            text_emotion.arousal,
+            global_emotion.arousal,
+        );
+
+        let dominance = text_dominance.map(|text_dominance| {
+            let global_dominance = global_emotion.dominance.unwrap_or(text_dominance);
+
+            blend(
+                source_emotion
+                    .and_then(|source| source.dominance)
+                    .unwrap_or(global_dominance),
+                text_dominance,
+                global_dominance,
+            )
+        });
+
+        EmotionPrediction::new(valence, arousal).with_dominance(dominance)
     }
 
     fn store_emotion_in_memory(
         &self,
         text: &str,
         final_emotion: &EmotionPrediction,
-        past_time: i64,
+        occurred_at: GameTime,
         source_id: Option<&str>,
     ) -> Result<(), EmotionPredictorError> {
         let effective_source_id = source_id.or(self.source_id.as_deref()).unwrap_or("unknown");
+        let effective_source_id =
+            SourceRegistry::resolve(effective_source_id).map_err(EmotionPredictorError::Inference)?;
 
         let record = MemoryRecord {
             id: Uuid::new_v4().to_string(),
@@ -159,7 +608,10 @@ impl MemoryEmotionEvaluator {
             content: text.to_string(),
             valence: final_emotion.valence,
             arousal: final_emotion.arousal,
-            past_time,
+            dominance: final_emotion.dominance,
+            occurred_at,
+            heard_from: None,
+            past_time: None,
         };
 
         MemoryStore::insert(&self.npc_id, record)
@@ -172,7 +624,11 @@ impl MemoryEmotionEvaluator {
 #[cfg(test)]
 mod tests {
     use super::MemoryEmotionEvaluator;
-    use crate::{ MemoryStore, NpcConfig};
+    use crate::config::BlendWeights;
+    use crate::{
+        EmotionEventKind, EmotionPrediction, GameClock, GameEvent, MemoryRecord, MemoryStore, NpcConfig, SourceProfile,
+        SourceRegistry, Trigger, TriggerAxis, TriggerCondition, TriggerStore,
+    };
 
     #[test]
     fn test_real_memory_emotion_evaluator_new() {
@@ -230,4 +686,458 @@ mod tests {
         assert_eq!(emotion.valence, evaluator.config.personality.valence);
         assert_eq!(emotion.arousal, evaluator.config.personality.arousal);
     }
+
+    #[test]
+    fn test_combine_emotions_uses_configured_blending() {
+        let mut config = NpcConfig::default();
+        config.blending.with_source = BlendWeights {
+            source: 0.8,
+            text: 0.1,
+            global: 0.1,
+        };
+        let evaluator = MemoryEmotionEvaluator::new(config, None).unwrap();
+
+        let text = EmotionPrediction::new(1.0, 0.0);
+        let source = EmotionPrediction::new(-1.0, 0.5);
+        let global = EmotionPrediction::new(0.0, 0.0);
+
+        let with_source = evaluator.combine_emotions_psychologically(&text, Some(&source), &global);
+        assert!((with_source.valence - -0.7).abs() < 1e-6);
+        assert!((with_source.arousal - 0.4).abs() < 1e-6);
+
+        let without_source = evaluator.combine_emotions_psychologically(&text, None, &global);
+        assert!((without_source.valence - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dominance_flows_through_memory() {
+        let mut config = NpcConfig::default();
+        config.personality.dominance = Some(0.0);
+        config.dominance.fallback = Some(-0.5);
+        let evaluator = MemoryEmotionEvaluator::new(config, None).unwrap();
+
+        let emotion = evaluator
+            .evaluate_npc_emotion(
+                "Drop your weapon!",
+                &EmotionPrediction::new(-0.4, 0.6),
+                0,
+                Some("guard"),
+            )
+            .unwrap();
+        let dominance = emotion.dominance.unwrap();
+        assert!(dominance < 0.0);
+
+        let records = MemoryStore::get_all(&evaluator.npc_id).unwrap();
+        assert_eq!(records[0].dominance, Some(dominance));
+
+        let current = evaluator.calculate_current_emotion().unwrap();
+        assert_eq!(current.dominance, Some(dominance));
+    }
+
+    #[test]
+    fn test_dominance_disabled_without_baseline() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        let emotion = evaluator
+            .evaluate_npc_emotion(
+                "Hello",
+                &EmotionPrediction::new(0.2, 0.1).with_dominance(Some(0.3)),
+                0,
+                None,
+            )
+            .unwrap();
+        assert_eq!(emotion.dominance, None);
+    }
+
+    #[test]
+    fn test_evaluation_updates_emotion_and_mood() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        let final_emotion = evaluator
+            .evaluate_npc_emotion(
+                "You saved my life!",
+                &EmotionPrediction::new(0.9, 0.5),
+                0,
+                Some("player"),
+            )
+            .unwrap();
+
+        let state = evaluator.current_affect_state().unwrap();
+        assert_eq!(state.emotion.valence, final_emotion.valence);
+        assert!(state.mood.valence > 0.0);
+        assert!(state.mood.valence < final_emotion.valence);
+    }
+
+    #[test]
+    fn test_current_emotion_calms_down_over_time() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        evaluator
+            .evaluate_npc_emotion(
+                "I'll burn your shop down!",
+                &EmotionPrediction::new(-0.9, 0.8),
+                0,
+                Some("player"),
+            )
+            .unwrap();
+
+        let angry = evaluator.calculate_current_emotion().unwrap();
+        let angry_at_player = evaluator.calculate_current_emotion_towards_source("player").unwrap();
+        let minutes = evaluator.time_to_baseline().unwrap().unwrap();
+        assert!(minutes > 0);
+
+        GameClock::advance(&evaluator.npc_id, minutes / 2).unwrap();
+        let calmer = evaluator.calculate_current_emotion().unwrap();
+        assert!(calmer.valence > angry.valence);
+        let calmer_at_player = evaluator.calculate_current_emotion_towards_source("player").unwrap();
+        assert!(calmer_at_player.valence > angry_at_player.valence);
+
+        GameClock::advance(&evaluator.npc_id, minutes - minutes / 2).unwrap();
+        assert_eq!(evaluator.time_to_baseline().unwrap(), Some(0));
+
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_reactivity_and_inertia_shape_reaction() {
+        let text = EmotionPrediction::new(0.8, 0.6);
+        let global = EmotionPrediction::new(0.0, 0.0);
+
+        let neutral = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let neutral_reaction = neutral.combine_emotions_psychologically(&text, None, &global);
+
+        let mut impulsive_config = NpcConfig::default();
+        impulsive_config.personality.reactivity = 2.0;
+        let impulsive = MemoryEmotionEvaluator::new(impulsive_config, None).unwrap();
+        let impulsive_reaction = impulsive.combine_emotions_psychologically(&text, None, &global);
+
+        let mut stoic_config = NpcConfig::default();
+        stoic_config.personality.inertia = 0.5;
+        let stoic = MemoryEmotionEvaluator::new(stoic_config, None).unwrap();
+        let stoic_reaction = stoic.combine_emotions_psychologically(&text, None, &global);
+
+        assert!(impulsive_reaction.valence > neutral_reaction.valence);
+        assert!((stoic_reaction.valence - neutral_reaction.valence * 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_negativity_bias_weighs_bad_memories() {
+        let mut config = NpcConfig::default();
+        config.personality.negativity_bias = 1.0;
+        let evaluator = MemoryEmotionEvaluator::new(config, None).unwrap();
+
+        let records: Vec<MemoryRecord> = [0.5, -0.5]
+            .iter()
+            .enumerate()
+            .map(|(index, valence)| MemoryRecord {
+                id: format!("bias-{}", index),
+                source_id: "player".to_string(),
+                content: String::new(),
+                valence: *valence,
+                arousal: 0.0,
+                dominance: None,
+                occurred_at: 0,
+                heard_from: None,
+                past_time: None,
+            })
+            .collect();
+
+        let emotion = evaluator.calculate_weighted_emotion(&records, 0);
+        assert!((emotion.valence - -0.5 / 3.0).abs() < 1e-6);
+
+        let mut volatile_config = NpcConfig::default();
+        volatile_config.personality.volatility = 1.0;
+        let volatile = MemoryEmotionEvaluator::new(volatile_config, None).unwrap();
+        let emotion = volatile.calculate_weighted_emotion(&records[..1], 0);
+        assert_eq!(emotion.valence, 1.0);
+    }
+
+    #[test]
+    fn test_event_is_stored_as_memory() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let event = GameEvent {
+            source_id: Some("thief".to_string()),
+            ..GameEvent::new("robbed")
+        };
+
+        let emotion = evaluator.evaluate_npc_event(&event, 0).unwrap();
+        assert!(emotion.valence < 0.0);
+
+        let records = MemoryStore::get_by_source(&evaluator.npc_id, "thief").unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(records[0].content.contains("robbed"));
+
+        assert!(evaluator
+            .evaluate_npc_event(&GameEvent::new("unknown_kind"), 0)
+            .is_err());
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_event_on_target_is_felt_through_affinity() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let enemy = MemoryRecord {
+            id: "enemy".to_string(),
+            source_id: "bandit".to_string(),
+            content: String::new(),
+            valence: -0.9,
+            arousal: 0.0,
+            dominance: None,
+            occurred_at: 0,
+            heard_from: None,
+            past_time: None,
+        };
+        MemoryStore::insert(&evaluator.npc_id, enemy).unwrap();
+
+        let robbed_bandit = GameEvent {
+            source_id: Some("thief".to_string()),
+            target_id: Some("bandit".to_string()),
+            ..GameEvent::new("robbed")
+        };
+        let emotion = evaluator.evaluate_npc_event(&robbed_bandit, 0).unwrap();
+        assert!(emotion.valence > 0.0);
+
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_observed_interaction_depends_on_target() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let attack = EmotionPrediction::new(-0.8, 0.6);
+
+        let friend = MemoryRecord {
+            id: "friend".to_string(),
+            source_id: "guard".to_string(),
+            content: String::new(),
+            valence: 0.9,
+            arousal: 0.0,
+            dominance: None,
+            occurred_at: 0,
+            heard_from: None,
+            past_time: None,
+        };
+        let enemy = MemoryRecord {
+            id: "enemy".to_string(),
+            source_id: "bandit".to_string(),
+            valence: -0.9,
+            ..friend.clone()
+        };
+        MemoryStore::insert(&evaluator.npc_id, friend).unwrap();
+        MemoryStore::insert(&evaluator.npc_id, enemy).unwrap();
+
+        evaluator
+            .observe_npc_interaction("attacks the guard", &attack, 0, "player", "guard")
+            .unwrap();
+        evaluator
+            .observe_npc_interaction("attacks the bandit", &attack, 0, "hero", "bandit")
+            .unwrap();
+
+        let towards_player = evaluator.calculate_current_emotion_towards_source("player").unwrap();
+        let towards_hero = evaluator.calculate_current_emotion_towards_source("hero").unwrap();
+        assert!(towards_player.valence < 0.0);
+        assert!(towards_hero.valence > towards_player.valence);
+
+        let records = MemoryStore::get_by_source(&evaluator.npc_id, "player").unwrap();
+        assert!(records[0].content.contains("guard"));
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_aliases_and_faction_spillover() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let hostile = EmotionPrediction::new(-0.8, 0.5);
+
+        let renamed = SourceProfile {
+            aliases: vec!["evaluator-aldric".to_string()],
+            ..SourceProfile::new("evaluator-player")
+        };
+        SourceRegistry::register(renamed).unwrap();
+        let bandit = SourceProfile {
+            factions: vec!["evaluator-bandits".to_string()],
+            ..SourceProfile::new("evaluator-bandit")
+        };
+        SourceRegistry::register(bandit).unwrap();
+
+        evaluator
+            .evaluate_npc_emotion("You again!", &hostile, 0, Some("evaluator-aldric"))
+            .unwrap();
+        evaluator
+            .evaluate_npc_emotion("Hand over your gold", &hostile, 0, Some("evaluator-bandit"))
+            .unwrap();
+
+        let records = MemoryStore::get_all(&evaluator.npc_id).unwrap();
+        assert_eq!(records[0].source_id, "evaluator-player");
+
+        let player = evaluator
+            .calculate_current_emotion_towards_source("evaluator-player")
+            .unwrap();
+        let alias = evaluator
+            .calculate_current_emotion_towards_source("evaluator-aldric")
+            .unwrap();
+        assert_eq!(player.valence, alias.valence);
+
+        let bandits = evaluator
+            .calculate_current_emotion_towards_source("evaluator-bandits")
+            .unwrap();
+        assert!(bandits.valence < 0.0);
+
+        let mut isolated_config = NpcConfig::default();
+        isolated_config.factions.spillover = 0.0;
+        let isolated = MemoryEmotionEvaluator::new_with_id(isolated_config, None, evaluator.npc_id.clone()).unwrap();
+        let bandits = isolated
+            .calculate_current_emotion_towards_source("evaluator-bandits")
+            .unwrap();
+        assert_eq!(bandits.valence, 0.0);
+
+        let mut weak_config = NpcConfig::default();
+        weak_config.factions.spillover = 0.2;
+        let weak = MemoryEmotionEvaluator::new_with_id(weak_config, None, evaluator.npc_id.clone()).unwrap();
+        let mut full_config = NpcConfig::default();
+        full_config.factions.spillover = 1.0;
+        let full = MemoryEmotionEvaluator::new_with_id(full_config, None, evaluator.npc_id.clone()).unwrap();
+
+        let weak_bandits = weak
+            .calculate_current_emotion_towards_source("evaluator-bandits")
+            .unwrap();
+        let full_bandits = full
+            .calculate_current_emotion_towards_source("evaluator-bandits")
+            .unwrap();
+        assert!(weak_bandits.valence < 0.0);
+        assert!(weak_bandits.valence > full_bandits.valence);
+
+        SourceRegistry::remove("evaluator-player").unwrap();
+        SourceRegistry::remove("evaluator-bandit").unwrap();
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_triggers_fire_and_clear() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let hostile = Trigger {
+            id: "hostile".to_string(),
+            source_id: Some("player".to_string()),
+            condition: TriggerCondition::Threshold {
+                axis: TriggerAxis::Valence,
+                above: None,
+                below: Some(-0.3),
+            },
+        };
+        TriggerStore::register(&evaluator.npc_id, hostile).unwrap();
+        assert!(evaluator.check_triggers().unwrap().is_empty());
+
+        evaluator
+            .evaluate_npc_emotion("I'll kill you", &EmotionPrediction::new(-1.0, 0.8), 0, Some("player"))
+            .unwrap();
+        let events: Vec<_> = TriggerStore::drain_events()
+            .unwrap()
+            .into_iter()
+            .filter(|event| event.npc_id == evaluator.npc_id)
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EmotionEventKind::Fired);
+        assert_eq!(events[0].source_id, Some("player".to_string()));
+
+        MemoryStore::clear(&evaluator.npc_id).unwrap();
+        let events = evaluator.check_triggers().unwrap();
+        assert_eq!(events[0].kind, EmotionEventKind::Cleared);
+
+        TriggerStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_explain_breaks_down_evaluation() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        evaluator
+            .evaluate_npc_emotion(
+                "You stole from me",
+                &EmotionPrediction::new(-0.8, 0.6),
+                0,
+                Some("player"),
+            )
+            .unwrap();
+        GameClock::set(&evaluator.npc_id, 60).unwrap();
+        evaluator
+            .evaluate_npc_emotion("Sorry", &EmotionPrediction::new(0.3, 0.0), 60, Some("player"))
+            .unwrap();
+
+        let predicted = EmotionPrediction::new(-0.9, 0.8);
+        let breakdown = evaluator
+            .explain_npc_emotion("I hate you", &predicted, 60, Some("player"), 1)
+            .unwrap();
+
+        assert_eq!(breakdown.predicted.valence, -0.9);
+        assert!(breakdown.source.is_some());
+        assert!(breakdown.overall.is_none());
+        let weights = &breakdown.weights;
+        assert!((weights.source + weights.text + weights.global - 1.0).abs() < 1e-6);
+        assert_eq!(breakdown.memories.len(), 1);
+        assert_eq!(breakdown.memories[0].age, 0);
+        assert!(breakdown.memories[0].share > 0.5);
+
+        let records = MemoryStore::get_by_source(&evaluator.npc_id, "player").unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[2].valence, breakdown.final_emotion.valence);
+
+        let anonymous = evaluator
+            .explain_npc_emotion("Who goes there?", &predicted, 60, None, 3)
+            .unwrap();
+        assert!(anonymous.source.is_none());
+        let overall = anonymous.overall.unwrap();
+        assert!(overall.valence < 0.0);
+        assert_ne!(overall.valence, anonymous.global.valence);
+
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_preview_leaves_memory_untouched() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        evaluator
+            .evaluate_npc_emotion("Welcome back", &EmotionPrediction::new(0.6, 0.2), 0, Some("player"))
+            .unwrap();
+        let mood = evaluator.current_affect_state().unwrap().mood;
+
+        let candidates = [EmotionPrediction::new(0.8, 0.3), EmotionPrediction::new(-0.8, 0.6)];
+        let previews = evaluator.preview_npc_emotions(&candidates, Some("player")).unwrap();
+        let single = evaluator.preview_npc_emotion(&candidates[1], Some("player")).unwrap();
+
+        assert!(previews[0].valence > previews[1].valence);
+        assert_eq!(single.valence, previews[1].valence);
+        assert_eq!(MemoryStore::get_memory_count(&evaluator.npc_id).unwrap(), 1);
+        assert_eq!(evaluator.current_affect_state().unwrap().mood.valence, mood.valence);
+
+        let evaluated = evaluator
+            .evaluate_npc_emotion("Go away", &candidates[1], 0, Some("player"))
+            .unwrap();
+        assert_eq!(evaluated.valence, single.valence);
+
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_rank_candidates_towards_source() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        evaluator
+            .evaluate_npc_emotion(
+                "You ruined everything",
+                &EmotionPrediction::new(-0.9, 0.7),
+                0,
+                Some("player"),
+            )
+            .unwrap();
+
+        let candidates = [EmotionPrediction::new(0.7, 0.3), EmotionPrediction::new(-0.6, 0.5)];
+
+        let ranked = evaluator.rank_candidates(&candidates, None, Some("player")).unwrap();
+        assert_eq!(ranked[0].index, 1);
+
+        let cheerful = EmotionPrediction::new(0.8, 0.4);
+        let ranked = evaluator
+            .rank_candidates(&candidates, Some(&cheerful), Some("player"))
+            .unwrap();
+        assert_eq!(ranked[0].index, 0);
+
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
 }
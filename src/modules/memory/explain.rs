@@ -0,0 +1,47 @@
+use crate::{EmotionPrediction, GameTime};
+use serde::Serialize;
+
+/// Share of each input in a blended emotion, after reactivity and normalization. `inertia` is the
+/// share of the mood kept on top of the blend.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AppliedBlendWeights {
+    pub source: f32,
+    pub text: f32,
+    pub global: f32,
+    pub inertia: f32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemoryContribution {
+    pub id: String,
+    pub source_id: String,
+    pub content: String,
+    pub valence: f32,
+    pub arousal: f32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dominance: Option<f32>,
+    pub age: GameTime,
+    /// Weight from the decay curve alone.
+    pub decay_weight: f32,
+    /// Share of the total weight once faction spillover and negativity bias are applied.
+    pub share: f32,
+}
+
+/// Every step of an evaluation, for answering "why does the NPC feel this way?".
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationBreakdown {
+    /// Emotion of the text or event before any blending.
+    pub predicted: EmotionPrediction,
+    /// Mood of the NPC at the time of the interaction.
+    pub global: EmotionPrediction,
+    /// Emotion towards the source, when the interaction has one.
+    pub source: Option<EmotionPrediction>,
+    /// Memory-based overall emotion, as returned by `get_current_emotion`, when the interaction has no
+    /// source. It is what `memories` explains then, but it is not blended: the blend uses `global`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overall: Option<EmotionPrediction>,
+    pub weights: AppliedBlendWeights,
+    pub final_emotion: EmotionPrediction,
+    /// Memories behind `source`, or behind `overall` without a source, most influential first.
+    pub memories: Vec<MemoryContribution>,
+}
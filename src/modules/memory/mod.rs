@@ -1,5 +1,7 @@
 pub mod evaluator;
+pub mod explain;
 pub mod store;
 
 pub use evaluator::MemoryEmotionEvaluator;
-pub use store::{MemoryRecord, MemoryStore};
+pub use explain::{AppliedBlendWeights, EvaluationBreakdown, MemoryContribution};
+pub use store::{MemoryRecord, MemorySnapshot, MemoryStore};
@@ -1,11 +1,28 @@
 use crate::api::types::NpcId;
+use crate::{AffectState, AffectStore, GameTime};
 use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::Mutex;
 
 lazy_static! {
-    static ref NPC_MEMORIES: Mutex<HashMap<NpcId, Vec<MemoryRecord>>> = Mutex::new(HashMap::new());
+    static ref NPC_MEMORIES: Mutex<HashMap<NpcId, NpcMemory>> = Mutex::new(HashMap::new());
+}
+
+#[derive(Debug, Clone, Default)]
+struct NpcMemory {
+    now: GameTime,
+    records: Vec<MemoryRecord>,
+}
+
+/// Everything needed to restore an NPC's memory: its records, its clock and its emotion and mood.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemorySnapshot {
+    pub now: GameTime,
+    pub records: Vec<MemoryRecord>,
+    /// `None` while the NPC is still at its baseline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affect: Option<AffectState>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,7 +32,26 @@ pub struct MemoryRecord {
     pub content: String,
     pub valence: f32,
     pub arousal: f32,
-    pub past_time: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dominance: Option<f32>,
+    #[serde(default)]
+    pub occurred_at: GameTime,
+    /// NPC this memory was heard from, for second-hand records received through gossip.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heard_from: Option<String>,
+    /// Relative age written by older save files, converted to `occurred_at` by `MemoryStore::import`.
+    #[serde(default, skip_serializing)]
+    pub past_time: Option<i64>,
+}
+
+impl MemoryRecord {
+    pub fn age(&self, now: GameTime) -> GameTime {
+        (now - self.occurred_at).max(0)
+    }
+
+    pub fn is_hearsay(&self) -> bool {
+        self.heard_from.is_some()
+    }
 }
 
 pub struct MemoryStore;
@@ -27,9 +63,22 @@ impl MemoryStore {
 
     pub fn insert(npc_id: &NpcId, record: MemoryRecord) -> Result<(), String> {
         let mut npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
-        let npc_memory = npc_memories.entry(npc_id.clone()).or_insert_with(Vec::new);
+        let npc_memory = npc_memories.entry(npc_id.clone()).or_default();
 
-        npc_memory.push(record);
+        npc_memory.records.push(record);
+
+        Ok(())
+    }
+
+    /// Replaces the record with the same id, or inserts it when there is none.
+    pub fn upsert(npc_id: &NpcId, record: MemoryRecord) -> Result<(), String> {
+        let mut npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
+        let npc_memory = npc_memories.entry(npc_id.clone()).or_default();
+
+        match npc_memory.records.iter_mut().find(|existing| existing.id == record.id) {
+            Some(existing) => *existing = record,
+            None => npc_memory.records.push(record),
+        }
 
         Ok(())
     }
@@ -37,13 +86,19 @@ impl MemoryStore {
     pub fn get_all(npc_id: &NpcId) -> Result<Vec<MemoryRecord>, String> {
         let npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
 
-        Ok(npc_memories.get(npc_id).cloned().unwrap_or_default())
+        Ok(npc_memories
+            .get(npc_id)
+            .map(|memory| memory.records.clone())
+            .unwrap_or_default())
     }
 
     pub fn get_by_source(npc_id: &NpcId, source_id: &str) -> Result<Vec<MemoryRecord>, String> {
         let npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
         let empty_vec = Vec::new();
-        let npc_memory = npc_memories.get(npc_id).unwrap_or(&empty_vec);
+        let npc_memory = npc_memories
+            .get(npc_id)
+            .map(|memory| &memory.records)
+            .unwrap_or(&empty_vec);
 
         Ok(npc_memory
             .iter()
@@ -52,7 +107,80 @@ impl MemoryStore {
             .collect())
     }
 
+    pub fn get_by_sources(npc_id: &NpcId, source_ids: &[String]) -> Result<Vec<MemoryRecord>, String> {
+        let npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
+
+        Ok(npc_memories
+            .get(npc_id)
+            .map(|memory| {
+                memory
+                    .records
+                    .iter()
+                    .filter(|record| source_ids.contains(&record.source_id))
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    pub fn now(npc_id: &NpcId) -> Result<GameTime, String> {
+        let npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
+
+        Ok(npc_memories.get(npc_id).map(|memory| memory.now).unwrap_or(0))
+    }
+
+    pub fn set_now(npc_id: &NpcId, now: GameTime) -> Result<(), String> {
+        let mut npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
+
+        npc_memories.entry(npc_id.clone()).or_default().now = now;
+
+        Ok(())
+    }
+
+    /// Reads and replaces the NPC clock under a single lock, so concurrent updates are not lost.
+    pub fn update_now<F>(npc_id: &NpcId, update: F) -> Result<GameTime, String>
+    where
+        F: FnOnce(GameTime) -> Result<GameTime, String>,
+    {
+        let mut npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
+        let npc_memory = npc_memories.entry(npc_id.clone()).or_default();
+
+        npc_memory.now = update(npc_memory.now)?;
+
+        Ok(npc_memory.now)
+    }
+
+    pub fn export(npc_id: &NpcId) -> Result<MemorySnapshot, String> {
+        let (now, records) = {
+            let npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
+            npc_memories
+                .get(npc_id)
+                .map(|memory| (memory.now, memory.records.clone()))
+                .unwrap_or_default()
+        };
+
+        Ok(MemorySnapshot {
+            now,
+            records,
+            affect: AffectStore::get_stored(npc_id)?,
+        })
+    }
+
+    /// Restores an exported snapshot, clock included.
+    pub fn restore(npc_id: &NpcId, snapshot: MemorySnapshot) -> Result<(), String> {
+        Self::import_at(npc_id, Some(snapshot.now), snapshot.records, snapshot.affect)
+    }
+
     pub fn import(npc_id: &NpcId, records: Vec<MemoryRecord>) -> Result<(), String> {
+        Self::import_at(npc_id, None, records, None)
+    }
+
+    fn import_at(
+        npc_id: &NpcId,
+        now: Option<GameTime>,
+        mut records: Vec<MemoryRecord>,
+        affect: Option<AffectState>,
+    ) -> Result<(), String> {
         for (index, record) in records.iter().enumerate() {
             if record.id.is_empty() {
                 return Err(format!("Record at index {} has empty ID", index));
@@ -69,22 +197,52 @@ impl MemoryStore {
                     record.id, record.arousal
                 ));
             }
+            if let Some(dominance) = record.dominance.filter(|dominance| !(-1.0..=1.0).contains(dominance)) {
+                return Err(format!(
+                    "Record {} has invalid dominance: {} (must be between -1.0 and 1.0)",
+                    record.id, dominance
+                ));
+            }
+            if let Some(past_time) = record.past_time.filter(|past_time| *past_time < 0) {
+                return Err(format!(
+                    "Record {} has invalid past_time: {} (must not be negative)",
+                    record.id, past_time
+                ));
+            }
         }
 
         let mut npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
-        let npc_memory = npc_memories.entry(npc_id.clone()).or_insert_with(Vec::new);
+        let npc_memory = npc_memories.entry(npc_id.clone()).or_default();
 
-        npc_memory.clear();
-        npc_memory.extend(records);
+        if let Some(now) = now {
+            npc_memory.now = now;
+        }
 
-        Ok(())
+        for record in records.iter_mut() {
+            if let Some(past_time) = record.past_time.take() {
+                record.occurred_at = npc_memory.now - past_time;
+            }
+        }
+
+        if let Some(latest) = records.iter().map(|record| record.occurred_at).max() {
+            npc_memory.now = npc_memory.now.max(latest);
+        }
+
+        npc_memory.records.clear();
+        npc_memory.records.extend(records);
+        drop(npc_memories);
+
+        match affect {
+            Some(affect) => AffectStore::set(npc_id, affect),
+            None => Ok(()),
+        }
     }
 
     pub fn clear(npc_id: &NpcId) -> Result<(), String> {
         let mut npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
 
         if let Some(npc_memory) = npc_memories.get_mut(npc_id) {
-            npc_memory.clear();
+            npc_memory.records.clear();
         }
 
         Ok(())
@@ -101,7 +259,7 @@ impl MemoryStore {
     pub fn get_memory_count(npc_id: &NpcId) -> Result<usize, String> {
         let npc_memories = NPC_MEMORIES.lock().map_err(|_| "Failed to acquire lock")?;
 
-        Ok(npc_memories.get(npc_id).map(|mem| mem.len()).unwrap_or(0))
+        Ok(npc_memories.get(npc_id).map(|mem| mem.records.len()).unwrap_or(0))
     }
 }
 
@@ -113,7 +271,8 @@ impl Default for MemoryStore {
 
 #[cfg(test)]
 mod tests {
-    use super::{MemoryRecord, MemoryStore};
+    use super::{MemoryRecord, MemorySnapshot, MemoryStore};
+    use crate::{AffectState, AffectStore, EmotionPrediction};
 
     #[test]
     fn test_memory_store_new() {
@@ -141,7 +300,10 @@ mod tests {
             content: "NPC 1 message".to_string(),
             valence: 0.5,
             arousal: -0.3,
-            past_time: 1000,
+            dominance: None,
+            occurred_at: 1000,
+            heard_from: None,
+            past_time: None,
         };
 
         let record2 = MemoryRecord {
@@ -150,7 +312,10 @@ mod tests {
             content: "NPC 2 message".to_string(),
             valence: -0.2,
             arousal: 0.7,
-            past_time: 2000,
+            dominance: Some(0.2),
+            occurred_at: 2000,
+            heard_from: None,
+            past_time: None,
         };
 
         MemoryStore::insert(&npc_id1, record1).unwrap();
@@ -168,5 +333,81 @@ mod tests {
         assert_eq!(npc2_records[0].content, "NPC 2 message");
     }
 
+    #[test]
+    fn test_memory_store_import_migrates_past_time() {
+        let npc_id = "npc-legacy-import".to_string();
+        MemoryStore::set_now(&npc_id, 500).unwrap();
+
+        let records: Vec<MemoryRecord> = serde_json::from_str(
+            r#"[{"id":"legacy-1","source_id":"player","content":"Old save","valence":0.4,"arousal":0.1,"past_time":120}]"#,
+        )
+        .unwrap();
+
+        MemoryStore::import(&npc_id, records).unwrap();
+
+        let imported = MemoryStore::get_all(&npc_id).unwrap();
+        assert_eq!(imported[0].occurred_at, 380);
+        assert_eq!(imported[0].past_time, None);
+        assert_eq!(imported[0].age(MemoryStore::now(&npc_id).unwrap()), 120);
+
+        let exported = serde_json::to_string(&imported).unwrap();
+        assert!(exported.contains("\"occurred_at\":380"));
+        assert!(!exported.contains("past_time"));
+    }
+
+    #[test]
+    fn test_memory_store_import_moves_now_to_latest_record() {
+        let npc_id = "npc-absolute-import".to_string();
+
+        let records: Vec<MemoryRecord> = serde_json::from_str(
+            r#"[{"id":"abs-1","source_id":"player","content":"Saved","valence":0.2,"arousal":0.0,"occurred_at":2880}]"#,
+        )
+        .unwrap();
 
+        MemoryStore::import(&npc_id, records).unwrap();
+
+        assert_eq!(MemoryStore::now(&npc_id).unwrap(), 2880);
+    }
+
+    #[test]
+    fn test_memory_store_export_restores_clock() {
+        let npc_id = "npc-snapshot-export".to_string();
+        let records: Vec<MemoryRecord> = serde_json::from_str(
+            r#"[{"id":"snap-1","source_id":"player","content":"Saved","valence":0.2,"arousal":0.0,"occurred_at":60}]"#,
+        )
+        .unwrap();
+        MemoryStore::import(&npc_id, records).unwrap();
+        MemoryStore::set_now(&npc_id, 1440).unwrap();
+
+        let mood = EmotionPrediction::new(-0.4, 0.3);
+        AffectStore::set(
+            &npc_id,
+            AffectState {
+                emotion: mood.clone(),
+                mood: mood.clone(),
+                updated_at: 1440,
+            },
+        )
+        .unwrap();
+
+        let snapshot = MemoryStore::export(&npc_id).unwrap();
+        assert_eq!(snapshot.now, 1440);
+        let snapshot: MemorySnapshot = serde_json::from_str(&serde_json::to_string(&snapshot).unwrap()).unwrap();
+
+        let restored_id = "npc-snapshot-restore".to_string();
+        MemoryStore::restore(&restored_id, snapshot).unwrap();
+        assert_eq!(MemoryStore::now(&restored_id).unwrap(), 1440);
+        assert_eq!(MemoryStore::get_all(&restored_id).unwrap()[0].age(1440), 1380);
+        let restored = AffectStore::get_stored(&restored_id).unwrap().unwrap();
+        assert_eq!(restored.mood.valence, -0.4);
+    }
+
+    #[test]
+    fn test_memory_store_update_now() {
+        let npc_id = "npc-update-now".to_string();
+
+        assert_eq!(MemoryStore::update_now(&npc_id, |now| Ok(now + 10)).unwrap(), 10);
+        assert!(MemoryStore::update_now(&npc_id, |_| Err("rejected".to_string())).is_err());
+        assert_eq!(MemoryStore::now(&npc_id).unwrap(), 10);
+    }
 }
@@ -1,2 +1,8 @@
+pub mod affect;
+pub mod appraisal;
+pub mod clock;
 pub mod emotion;
 pub mod memory;
+pub mod social;
+pub mod source;
+pub mod trigger;
@@ -0,0 +1,262 @@
+use crate::api::types::NpcId;
+use crate::{
+    AffectState, AffectStore, EmotionPrediction, EmotionPredictorError, MemoryEmotionEvaluator, MemoryRecord,
+    MemoryStore,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Source, and id, of the single memory each NPC keeps of emotional contagion.
+pub const CONTAGION_SOURCE_ID: &str = "contagion";
+
+/// NPCs sharing a space, with `weights[i][j]` the influence of `npc_ids[j]` on `npc_ids[i]`.
+/// Without weights, everyone influences everyone else with weight 1.0.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContagionGroup {
+    pub npc_ids: Vec<NpcId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub weights: Option<Vec<Vec<f32>>>,
+}
+
+impl ContagionGroup {
+    pub fn new(npc_ids: Vec<NpcId>) -> Self {
+        Self { npc_ids, weights: None }
+    }
+
+    pub fn weight(&self, i: usize, j: usize) -> f32 {
+        if i == j {
+            return 0.0;
+        }
+
+        self.weights.as_ref().map_or(1.0, |weights| weights[i][j])
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        if let Some(duplicate) = self.npc_ids.iter().find(|npc_id| !seen.insert(*npc_id)) {
+            return Err(format!("NPC '{}' appears more than once in the group", duplicate));
+        }
+
+        if let Some(weights) = &self.weights {
+            let size = self.npc_ids.len();
+            if weights.len() != size || weights.iter().any(|row| row.len() != size) {
+                return Err(format!("Contagion weights must be a {}x{} matrix", size, size));
+            }
+
+            if let Some(weight) = weights.iter().flatten().find(|weight| !(0.0..=1.0).contains(*weight)) {
+                return Err(format!(
+                    "Contagion weight has value {}, but must be between 0.0 and 1.0",
+                    weight
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Emotional contagion between NPCs of the same group.
+pub struct Contagion;
+
+impl Contagion {
+    /// Pulls each emotion towards the weighted emotion of the rest of the group, by the NPC's
+    /// susceptibility times its total incoming weight (capped at 1.0). All NPCs update from the same
+    /// previous emotions, so the order of `npc_ids` does not matter.
+    pub fn blend(
+        emotions: &[EmotionPrediction],
+        susceptibilities: &[f32],
+        group: &ContagionGroup,
+    ) -> Vec<EmotionPrediction> {
+        emotions
+            .iter()
+            .enumerate()
+            .map(|(i, emotion)| {
+                let mut total_weight = 0.0;
+                let mut valence = 0.0;
+                let mut arousal = 0.0;
+                let mut dominance = 0.0;
+                let mut dominance_weight = 0.0;
+
+                for (j, other) in emotions.iter().enumerate() {
+                    let weight = group.weight(i, j);
+                    total_weight += weight;
+                    valence += other.valence * weight;
+                    arousal += other.arousal * weight;
+
+                    if let Some(other_dominance) = other.dominance {
+                        dominance += other_dominance * weight;
+                        dominance_weight += weight;
+                    }
+                }
+
+                if total_weight <= 0.0 {
+                    return emotion.clone();
+                }
+
+                let pull = susceptibilities[i] * total_weight.min(1.0);
+                let towards = |own: f32, group: f32| (own + (group - own) * pull).clamp(-1.0, 1.0);
+
+                EmotionPrediction::new(
+                    towards(emotion.valence, valence / total_weight),
+                    towards(emotion.arousal, arousal / total_weight),
+                )
+                .with_dominance(emotion.dominance.map(|own| {
+                    if dominance_weight > 0.0 {
+                        towards(own, dominance / dominance_weight)
+                    } else {
+                        own
+                    }
+                }))
+            })
+            .collect()
+    }
+
+    /// Runs one contagion step over the current emotions of the evaluators, in `group.npc_ids` order.
+    /// Each blended emotion replaces the NPC's single memory from [`CONTAGION_SOURCE_ID`], so it shows
+    /// up in the NPC's current emotion and fades like any other memory without piling up every tick,
+    /// and becomes its affect state's emotion.
+    /// Triggers are checked for every NPC of the group afterwards.
+    pub fn step(
+        evaluators: &[&MemoryEmotionEvaluator],
+        group: &ContagionGroup,
+    ) -> Result<Vec<AffectState>, EmotionPredictorError> {
+        group.validate().map_err(EmotionPredictorError::Inference)?;
+
+        let emotions = evaluators
+            .iter()
+            .map(|evaluator| evaluator.calculate_current_emotion())
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut states = evaluators
+            .iter()
+            .map(|evaluator| evaluator.current_affect_state())
+            .collect::<Result<Vec<_>, _>>()?;
+        let susceptibilities: Vec<f32> = evaluators
+            .iter()
+            .map(|evaluator| evaluator.config.personality.susceptibility)
+            .collect();
+
+        for ((evaluator, state), emotion) in
+            evaluators
+                .iter()
+                .zip(states.iter_mut())
+                .zip(Self::blend(&emotions, &susceptibilities, group))
+        {
+            let record = MemoryRecord {
+                id: CONTAGION_SOURCE_ID.to_string(),
+                source_id: CONTAGION_SOURCE_ID.to_string(),
+                content: "[contagion]".to_string(),
+                valence: emotion.valence,
+                arousal: emotion.arousal,
+                dominance: emotion.dominance,
+                occurred_at: evaluator.current_time()?,
+                heard_from: None,
+                past_time: None,
+            };
+            MemoryStore::upsert(&evaluator.npc_id, record)
+                .map_err(|e| EmotionPredictorError::Inference(format!("Failed to store memory: {}", e)))?;
+
+            state.emotion = emotion;
+            AffectStore::set(&evaluator.npc_id, state.clone())
+                .map_err(|e| EmotionPredictorError::Inference(format!("Failed to store affect state: {}", e)))?;
+        }
+
+        for evaluator in evaluators {
+            evaluator.check_triggers()?;
+        }
+
+        Ok(states)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Contagion, ContagionGroup, CONTAGION_SOURCE_ID};
+    use crate::{
+        EmotionPrediction, MemoryEmotionEvaluator, MemoryStore, NpcConfig, Trigger, TriggerAxis, TriggerCondition,
+        TriggerStore,
+    };
+
+    fn group(size: usize) -> ContagionGroup {
+        ContagionGroup::new((0..size).map(|index| format!("npc-{}", index)).collect())
+    }
+
+    #[test]
+    fn test_blend_pulls_towards_group() {
+        let emotions = vec![EmotionPrediction::new(-0.8, 0.8), EmotionPrediction::new(0.2, 0.0)];
+
+        let blended = Contagion::blend(&emotions, &[0.0, 0.5], &group(2));
+
+        assert_eq!(blended[0].valence, -0.8);
+        assert!((blended[1].valence - -0.3).abs() < 1e-6);
+        assert!((blended[1].arousal - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_blend_uses_pairwise_weights() {
+        let emotions = vec![
+            EmotionPrediction::new(0.0, 0.0),
+            EmotionPrediction::new(1.0, 0.0),
+            EmotionPrediction::new(-1.0, 0.0),
+        ];
+        let weighted = ContagionGroup {
+            weights: Some(vec![vec![0.0, 0.5, 0.0], vec![0.0; 3], vec![0.0; 3]]),
+            ..group(3)
+        };
+
+        let blended = Contagion::blend(&emotions, &[1.0, 1.0, 1.0], &weighted);
+
+        assert!((blended[0].valence - 0.5).abs() < 1e-6);
+        assert_eq!(blended[1].valence, 1.0);
+        assert_eq!(blended[2].valence, -1.0);
+    }
+
+    #[test]
+    fn test_group_validation() {
+        assert!(group(3).validate().is_ok());
+        assert!(ContagionGroup::new(vec!["a".to_string(), "a".to_string()])
+            .validate()
+            .is_err());
+
+        let bad_shape = ContagionGroup {
+            weights: Some(vec![vec![0.0, 1.0]]),
+            ..group(2)
+        };
+        assert!(bad_shape.validate().is_err());
+    }
+
+    #[test]
+    fn test_step_updates_affect_state() {
+        let calm = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let tense = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        tense
+            .evaluate_npc_emotion("Run!", &EmotionPrediction::new(-0.7, 0.9), 0, None)
+            .unwrap();
+
+        let nervous = Trigger {
+            id: "nervous".to_string(),
+            source_id: None,
+            condition: TriggerCondition::Threshold {
+                axis: TriggerAxis::Arousal,
+                above: Some(0.05),
+                below: None,
+            },
+        };
+        TriggerStore::register(&calm.npc_id, nervous).unwrap();
+
+        let crowd = ContagionGroup::new(vec![calm.npc_id.clone(), tense.npc_id.clone()]);
+        let states = Contagion::step(&[&calm, &tense], &crowd).unwrap();
+
+        assert!(states[0].emotion.arousal > 0.0);
+        assert!(calm.current_affect_state().unwrap().emotion.arousal > 0.0);
+        assert!(calm.calculate_current_emotion().unwrap().arousal > 0.0);
+
+        Contagion::step(&[&calm, &tense], &crowd).unwrap();
+        let records = MemoryStore::get_all(&calm.npc_id).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].source_id, CONTAGION_SOURCE_ID);
+        assert!(TriggerStore::drain_events()
+            .unwrap()
+            .iter()
+            .any(|event| event.npc_id == calm.npc_id && event.trigger_id == "nervous"));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{EmotionPredictorError, MemoryEmotionEvaluator, MemoryRecord, MemoryStore, Relationship, SourceRegistry};
+use serde::Serialize;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GossipOutcome {
+    pub shared: usize,
+    pub trust: f32,
+}
+
+/// Second-hand sharing of memories between NPCs.
+pub struct Gossip;
+
+impl Gossip {
+    /// How much the listener believes the speaker: its relationship trust, 0.5 for strangers.
+    pub fn trust(listener: &MemoryEmotionEvaluator, speaker_id: &str) -> Result<f32, EmotionPredictorError> {
+        Ok(Relationship::between(listener, speaker_id)?.trust)
+    }
+
+    /// Shares the speaker's most recent memories about `source_id` with the listener. The listener stores
+    /// them as hearsay, pulled towards its own baseline by `credulity * trust`, at its current game time.
+    /// Memories the listener already has, first-hand or heard before, are skipped. The listener's
+    /// triggers are checked afterwards.
+    pub fn share(
+        speaker: &MemoryEmotionEvaluator,
+        listener: &MemoryEmotionEvaluator,
+        source_id: &str,
+    ) -> Result<GossipOutcome, EmotionPredictorError> {
+        if speaker.npc_id == listener.npc_id {
+            return Err(EmotionPredictorError::Inference(
+                "An NPC cannot gossip with itself".to_string(),
+            ));
+        }
+
+        let source_id = SourceRegistry::resolve(source_id).map_err(EmotionPredictorError::Inference)?;
+
+        let mut records = speaker.memories_about(&source_id)?;
+        records.sort_by_key(|record| std::cmp::Reverse(record.occurred_at));
+        records.truncate(speaker.config.gossip.max_shared);
+
+        let known: HashSet<String> = MemoryStore::get_all(&listener.npc_id)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get memory records: {}", e)))?
+            .into_iter()
+            .map(|record| record.id)
+            .collect();
+
+        let trust = Self::trust(listener, &speaker.npc_id)?;
+        let weight = listener.config.gossip.credulity * trust;
+        let now = MemoryStore::now(&listener.npc_id)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get game time: {}", e)))?;
+
+        let speaker_personality = &speaker.config.personality;
+        let listener_personality = &listener.config.personality;
+
+        let mut shared = 0;
+        for record in records.into_iter().filter(|record| !known.contains(&record.id)) {
+            let dominance = listener_personality.dominance.and_then(|listener_dominance| {
+                record.dominance.map(|dominance| {
+                    let deviation = dominance - speaker_personality.dominance.unwrap_or(0.0);
+                    (listener_dominance + deviation * weight).clamp(-1.0, 1.0)
+                })
+            });
+
+            let hearsay = MemoryRecord {
+                source_id: source_id.clone(),
+                valence: (listener_personality.valence + (record.valence - speaker_personality.valence) * weight)
+                    .clamp(-1.0, 1.0),
+                arousal: (listener_personality.arousal + (record.arousal - speaker_personality.arousal) * weight)
+                    .clamp(-1.0, 1.0),
+                dominance,
+                occurred_at: now,
+                heard_from: Some(speaker.npc_id.clone()),
+                past_time: None,
+                ..record
+            };
+
+            MemoryStore::insert(&listener.npc_id, hearsay)
+                .map_err(|e| EmotionPredictorError::Inference(format!("Failed to store memory: {}", e)))?;
+            shared += 1;
+        }
+
+        listener.check_triggers()?;
+
+        Ok(GossipOutcome { shared, trust })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gossip;
+    use crate::{EmotionPrediction, MemoryEmotionEvaluator, MemoryStore, NpcConfig};
+
+    #[test]
+    fn test_gossip_spreads_reputation() {
+        let speaker = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let listener = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        speaker
+            .evaluate_npc_emotion(
+                "You burned my farm!",
+                &EmotionPrediction::new(-0.9, 0.7),
+                0,
+                Some("player"),
+            )
+            .unwrap();
+
+        let outcome = Gossip::share(&speaker, &listener, "player").unwrap();
+        assert_eq!(outcome.shared, 1);
+        assert_eq!(outcome.trust, 0.5);
+
+        let heard = MemoryStore::get_by_source(&listener.npc_id, "player").unwrap();
+        assert!(heard[0].is_hearsay());
+        let told = MemoryStore::get_by_source(&speaker.npc_id, "player").unwrap();
+        assert!(heard[0].valence < 0.0);
+        assert!(heard[0].valence > told[0].valence);
+
+        let again = Gossip::share(&speaker, &listener, "player").unwrap();
+        assert_eq!(again.shared, 0);
+        assert!(Gossip::share(&speaker, &speaker, "player").is_err());
+
+        MemoryStore::remove_npc(&speaker.npc_id).unwrap();
+        MemoryStore::remove_npc(&listener.npc_id).unwrap();
+    }
+
+    #[test]
+    fn test_distrusted_speaker_is_not_believed() {
+        let speaker = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+        let listener = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        listener
+            .evaluate_npc_emotion("Liar!", &EmotionPrediction::new(-1.0, 0.5), 0, Some(&speaker.npc_id))
+            .unwrap();
+        speaker
+            .evaluate_npc_emotion("Thank you, hero", &EmotionPrediction::new(0.9, 0.3), 0, Some("player"))
+            .unwrap();
+
+        let outcome = Gossip::share(&speaker, &listener, "player").unwrap();
+        assert!(outcome.trust < 0.5);
+
+        let heard = MemoryStore::get_by_source(&listener.npc_id, "player").unwrap();
+        assert!(heard[0].valence < 0.2);
+
+        MemoryStore::remove_npc(&speaker.npc_id).unwrap();
+        MemoryStore::remove_npc(&listener.npc_id).unwrap();
+    }
+}
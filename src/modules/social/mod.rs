@@ -0,0 +1,7 @@
+pub mod contagion;
+pub mod gossip;
+pub mod relationship;
+
+pub use contagion::{Contagion, ContagionGroup};
+pub use gossip::{Gossip, GossipOutcome};
+pub use relationship::Relationship;
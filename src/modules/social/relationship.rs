@@ -0,0 +1,132 @@
+use crate::{EmotionPredictorError, GameTime, MemoryEmotionEvaluator, MemoryStore, SourceRegistry};
+use serde::Serialize;
+
+/// How an NPC relates to a source, derived from its memories about that source.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Relationship {
+    pub source_id: String,
+    /// 0.0 for strangers, approaching 1.0 with many recent first-hand interactions.
+    pub familiarity: f32,
+    /// Decay-weighted share of pleasant interactions, starting from 0.5 with no history.
+    pub trust: f32,
+    /// How much more pleasant the NPC feels about the source than its baseline, from -1.0 to 1.0.
+    pub affinity: f32,
+    /// Decay-weighted standard deviation of remembered valence, from 0.0 (steady) to 1.0.
+    pub volatility: f32,
+    pub interactions: usize,
+    pub hearsay: usize,
+    pub last_interaction: Option<GameTime>,
+}
+
+impl Relationship {
+    pub fn between(evaluator: &MemoryEmotionEvaluator, source_id: &str) -> Result<Self, EmotionPredictorError> {
+        let source_id = SourceRegistry::resolve(source_id).map_err(EmotionPredictorError::Inference)?;
+        let records = evaluator.memories_about(&source_id)?;
+        let now = MemoryStore::now(&evaluator.npc_id)
+            .map_err(|e| EmotionPredictorError::Inference(format!("Failed to get game time: {}", e)))?;
+
+        let config = &evaluator.config.relationship;
+        let personality = &evaluator.config.personality;
+        let decay_curve = evaluator.config.memory.curve();
+        let negativity_weight = 1.0 + personality.negativity_bias;
+
+        let mut familiarity_weight = 0.0;
+        let mut pleasant_weight = 0.0;
+        let mut total_weight = 0.0;
+        let mut weighted_valence = 0.0;
+        let mut weighted_squared_valence = 0.0;
+
+        for record in &records {
+            let weight = decay_curve.weight(record.age(now));
+
+            if !record.is_hearsay() {
+                familiarity_weight += weight;
+            }
+
+            if record.valence >= personality.valence {
+                pleasant_weight += weight;
+                total_weight += weight;
+            } else {
+                total_weight += weight * negativity_weight;
+            }
+
+            weighted_valence += record.valence * weight;
+            weighted_squared_valence += record.valence * record.valence * weight;
+        }
+
+        let decay_weight: f32 = records.iter().map(|record| decay_curve.weight(record.age(now))).sum();
+        let volatility = if records.len() > 1 && decay_weight > 0.0 {
+            let mean = weighted_valence / decay_weight;
+            (weighted_squared_valence / decay_weight - mean * mean).max(0.0).sqrt()
+        } else {
+            0.0
+        };
+
+        let towards_source = evaluator.calculate_current_emotion_towards_source(&source_id)?;
+        let interactions = records.iter().filter(|record| !record.is_hearsay()).count();
+
+        Ok(Self {
+            familiarity: 1.0 - (-familiarity_weight / config.familiarity_saturation).exp(),
+            trust: (pleasant_weight + 0.5 * config.trust_prior) / (total_weight + config.trust_prior),
+            affinity: (towards_source.valence - personality.valence).clamp(-1.0, 1.0),
+            volatility: volatility.min(1.0),
+            interactions,
+            hearsay: records.len() - interactions,
+            last_interaction: records.iter().map(|record| record.occurred_at).max(),
+            source_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Relationship;
+    use crate::{EmotionPrediction, GameClock, MemoryEmotionEvaluator, MemoryStore, NpcConfig};
+
+    #[test]
+    fn test_stranger_relationship() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        let relationship = Relationship::between(&evaluator, "stranger").unwrap();
+
+        assert_eq!(relationship.familiarity, 0.0);
+        assert_eq!(relationship.trust, 0.5);
+        assert_eq!(relationship.affinity, 0.0);
+        assert_eq!(relationship.volatility, 0.0);
+        assert_eq!(relationship.last_interaction, None);
+    }
+
+    #[test]
+    fn test_consistent_friend_and_erratic_acquaintance() {
+        let evaluator = MemoryEmotionEvaluator::new(NpcConfig::default(), None).unwrap();
+
+        for minute in 0..4 {
+            evaluator
+                .evaluate_npc_emotion(
+                    "Good to see you",
+                    &EmotionPrediction::new(0.7, 0.2),
+                    minute,
+                    Some("friend"),
+                )
+                .unwrap();
+            let valence = if minute % 2 == 0 { 0.9 } else { -0.9 };
+            evaluator
+                .evaluate_npc_emotion("...", &EmotionPrediction::new(valence, 0.5), minute, Some("erratic"))
+                .unwrap();
+        }
+        GameClock::set(&evaluator.npc_id, 4).unwrap();
+
+        let friend = Relationship::between(&evaluator, "friend").unwrap();
+        let erratic = Relationship::between(&evaluator, "erratic").unwrap();
+
+        assert_eq!(friend.interactions, 4);
+        assert_eq!(friend.last_interaction, Some(3));
+        assert!(friend.familiarity > 0.0);
+        assert!(friend.trust > 0.7);
+        assert!(friend.affinity > 0.0);
+        assert!(erratic.trust < friend.trust);
+        assert!(erratic.volatility > friend.volatility);
+
+        MemoryStore::remove_npc(&evaluator.npc_id).unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref SOURCES: Mutex<Sources> = Mutex::new(Sources::default());
+}
+
+#[derive(Debug, Default)]
+struct Sources {
+    profiles: HashMap<String, SourceProfile>,
+    aliases: HashMap<String, String>,
+}
+
+impl Sources {
+    fn resolve(&self, source_id: &str) -> String {
+        self.aliases
+            .get(source_id)
+            .cloned()
+            .unwrap_or_else(|| source_id.to_string())
+    }
+
+    fn identities(&self, source_id: &str) -> Vec<String> {
+        let mut identities = vec![source_id.to_string()];
+
+        if let Some(profile) = self.profiles.get(source_id) {
+            identities.extend(profile.aliases.iter().cloned());
+        }
+
+        identities
+    }
+}
+
+/// A source of interactions known across all NPCs, e.g. the player or a named bandit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceProfile {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub factions: Vec<String>,
+}
+
+impl SourceProfile {
+    pub fn new(id: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            display_name: None,
+            aliases: Vec::new(),
+            factions: Vec::new(),
+        }
+    }
+}
+
+/// World-wide registry of source ids, their aliases and the factions they belong to.
+/// Unregistered ids resolve to themselves, so registering sources is optional.
+pub struct SourceRegistry;
+
+impl SourceRegistry {
+    /// Registers a source, replacing any previous profile with the same id.
+    pub fn register(profile: SourceProfile) -> Result<(), String> {
+        if profile.id.trim().is_empty() {
+            return Err("Source ID must not be empty".to_string());
+        }
+
+        let mut sources = SOURCES.lock().map_err(|_| "Failed to acquire lock")?;
+
+        if let Some(owner) = sources.aliases.get(&profile.id) {
+            return Err(format!("Source ID '{}' is already an alias of '{}'", profile.id, owner));
+        }
+
+        for alias in &profile.aliases {
+            if alias == &profile.id || sources.profiles.contains_key(alias) {
+                return Err(format!("Alias '{}' is already a source ID", alias));
+            }
+            if let Some(owner) = sources.aliases.get(alias).filter(|owner| *owner != &profile.id) {
+                return Err(format!("Alias '{}' already belongs to '{}'", alias, owner));
+            }
+        }
+
+        if let Some(previous) = sources.profiles.remove(&profile.id) {
+            for alias in previous.aliases {
+                sources.aliases.remove(&alias);
+            }
+        }
+
+        for alias in &profile.aliases {
+            sources.aliases.insert(alias.clone(), profile.id.clone());
+        }
+        sources.profiles.insert(profile.id.clone(), profile);
+
+        Ok(())
+    }
+
+    pub fn remove(source_id: &str) -> Result<(), String> {
+        let mut sources = SOURCES.lock().map_err(|_| "Failed to acquire lock")?;
+
+        if let Some(profile) = sources.profiles.remove(source_id) {
+            for alias in profile.aliases {
+                sources.aliases.remove(&alias);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get(source_id: &str) -> Result<Option<SourceProfile>, String> {
+        let sources = SOURCES.lock().map_err(|_| "Failed to acquire lock")?;
+        let source_id = sources.resolve(source_id);
+
+        Ok(sources.profiles.get(&source_id).cloned())
+    }
+
+    /// Canonical id of a source id or alias.
+    pub fn resolve(source_id: &str) -> Result<String, String> {
+        let sources = SOURCES.lock().map_err(|_| "Failed to acquire lock")?;
+
+        Ok(sources.resolve(source_id))
+    }
+
+    /// Every id memories about the source may have been stored under: its canonical id and its aliases.
+    pub fn identities(source_id: &str) -> Result<Vec<String>, String> {
+        let sources = SOURCES.lock().map_err(|_| "Failed to acquire lock")?;
+        let source_id = sources.resolve(source_id);
+
+        Ok(sources.identities(&source_id))
+    }
+
+    /// Canonical ids of the registered sources belonging to a faction.
+    pub fn members(faction_id: &str) -> Result<Vec<String>, String> {
+        let sources = SOURCES.lock().map_err(|_| "Failed to acquire lock")?;
+        let faction_id = sources.resolve(faction_id);
+
+        let mut members: Vec<String> = sources
+            .profiles
+            .values()
+            .filter(|profile| {
+                profile
+                    .factions
+                    .iter()
+                    .any(|faction| sources.resolve(faction) == faction_id)
+            })
+            .map(|profile| profile.id.clone())
+            .collect();
+        members.sort();
+
+        Ok(members)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SourceProfile, SourceRegistry};
+
+    #[test]
+    fn test_aliases_resolve_to_source() {
+        let profile = SourceProfile {
+            display_name: Some("Aldric".to_string()),
+            aliases: vec!["registry-aldric".to_string()],
+            ..SourceProfile::new("registry-player")
+        };
+        SourceRegistry::register(profile).unwrap();
+
+        assert_eq!(SourceRegistry::resolve("registry-aldric").unwrap(), "registry-player");
+        assert_eq!(SourceRegistry::resolve("registry-unknown").unwrap(), "registry-unknown");
+        assert_eq!(
+            SourceRegistry::identities("registry-aldric").unwrap(),
+            vec!["registry-player".to_string(), "registry-aldric".to_string()]
+        );
+        assert_eq!(
+            SourceRegistry::get("registry-aldric").unwrap().unwrap().display_name,
+            Some("Aldric".to_string())
+        );
+
+        SourceRegistry::remove("registry-player").unwrap();
+        assert_eq!(SourceRegistry::resolve("registry-aldric").unwrap(), "registry-aldric");
+    }
+
+    #[test]
+    fn test_alias_conflicts_are_rejected() {
+        let first = SourceProfile {
+            aliases: vec!["registry-shared".to_string()],
+            ..SourceProfile::new("registry-first")
+        };
+        let second = SourceProfile {
+            aliases: vec!["registry-shared".to_string()],
+            ..SourceProfile::new("registry-second")
+        };
+
+        SourceRegistry::register(first).unwrap();
+        assert!(SourceRegistry::register(second).is_err());
+        assert!(SourceRegistry::register(SourceProfile::new("registry-shared")).is_err());
+        assert!(SourceRegistry::register(SourceProfile::new(" ")).is_err());
+
+        SourceRegistry::remove("registry-first").unwrap();
+    }
+
+    #[test]
+    fn test_faction_members() {
+        for id in ["registry-bandit-1", "registry-bandit-2"] {
+            let profile = SourceProfile {
+                factions: vec!["registry-bandits".to_string()],
+                ..SourceProfile::new(id)
+            };
+            SourceRegistry::register(profile).unwrap();
+        }
+
+        assert_eq!(
+            SourceRegistry::members("registry-bandits").unwrap(),
+            vec!["registry-bandit-1".to_string(), "registry-bandit-2".to_string()]
+        );
+        assert!(SourceRegistry::members("registry-guards").unwrap().is_empty());
+
+        SourceRegistry::remove("registry-bandit-1").unwrap();
+        SourceRegistry::remove("registry-bandit-2").unwrap();
+    }
+}
@@ -0,0 +1,210 @@
+use crate::{EmotionLabel, EmotionPrediction};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerAxis {
+    Valence,
+    Arousal,
+    Dominance,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TriggerCondition {
+    /// Emotion within `radius` of a point of the circumplex.
+    Region { valence: f32, arousal: f32, radius: f32 },
+    /// Primary label equal to `label`.
+    Label { label: String },
+    /// Value on one axis above `above` and/or below `below`.
+    Threshold {
+        axis: TriggerAxis,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        above: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        below: Option<f32>,
+    },
+}
+
+impl TriggerCondition {
+    pub fn matches(&self, emotion: &EmotionPrediction, label: &EmotionLabel) -> bool {
+        match self {
+            TriggerCondition::Region {
+                valence,
+                arousal,
+                radius,
+            } => {
+                let valence_delta = emotion.valence - valence;
+                let arousal_delta = emotion.arousal - arousal;
+                (valence_delta * valence_delta + arousal_delta * arousal_delta).sqrt() <= *radius
+            }
+            TriggerCondition::Label { label: expected } => &label.primary == expected,
+            TriggerCondition::Threshold { axis, above, below } => {
+                let value = match axis {
+                    TriggerAxis::Valence => Some(emotion.valence),
+                    TriggerAxis::Arousal => Some(emotion.arousal),
+                    TriggerAxis::Dominance => emotion.dominance,
+                };
+
+                value.is_some_and(|value| {
+                    above.is_none_or(|above| value > above) && below.is_none_or(|below| value < below)
+                })
+            }
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            TriggerCondition::Region {
+                valence,
+                arousal,
+                radius,
+            } => {
+                if !valence.is_finite() || !arousal.is_finite() {
+                    return Err(format!(
+                        "Region trigger center ({}, {}) must be finite",
+                        valence, arousal
+                    ));
+                }
+                if !radius.is_finite() || *radius <= 0.0 {
+                    return Err(format!(
+                        "Region trigger 'radius' has value {}, but must be positive",
+                        radius
+                    ));
+                }
+                Ok(())
+            }
+            TriggerCondition::Label { label } if label.trim().is_empty() => {
+                Err("Label trigger 'label' must not be empty".to_string())
+            }
+            TriggerCondition::Label { .. } => Ok(()),
+            TriggerCondition::Threshold { above, below, .. } => {
+                if above.is_none() && below.is_none() {
+                    return Err("Threshold trigger needs 'above', 'below' or both".to_string());
+                }
+                if let Some(value) = above.iter().chain(below).find(|value| !value.is_finite()) {
+                    return Err(format!(
+                        "Threshold trigger bound has value {}, but must be finite",
+                        value
+                    ));
+                }
+                if let (Some(above), Some(below)) = (above, below) {
+                    if above >= below {
+                        return Err(format!(
+                            "Threshold trigger 'above' ({}) must be lower than 'below' ({}), or it can never match",
+                            above, below
+                        ));
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Condition watched on the NPC's overall emotion, or on its emotion towards `source_id` when set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Trigger {
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    #[serde(flatten)]
+    pub condition: TriggerCondition,
+}
+
+impl Trigger {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.id.trim().is_empty() {
+            return Err("Trigger ID must not be empty".to_string());
+        }
+
+        self.condition
+            .validate()
+            .map_err(|e| format!("Trigger '{}': {}", self.id, e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Trigger, TriggerAxis, TriggerCondition};
+    use crate::{EmotionPrediction, LabelingConfig};
+
+    fn matches(condition: &TriggerCondition, emotion: EmotionPrediction) -> bool {
+        let label = LabelingConfig::default().label(&emotion);
+        condition.matches(&emotion, &label)
+    }
+
+    #[test]
+    fn test_region_and_label_conditions() {
+        let region = TriggerCondition::Region {
+            valence: -0.7,
+            arousal: 0.7,
+            radius: 0.3,
+        };
+        assert!(matches(&region, EmotionPrediction::new(-0.6, 0.8)));
+        assert!(!matches(&region, EmotionPrediction::new(0.6, 0.8)));
+
+        let label = TriggerCondition::Label {
+            label: "happy".to_string(),
+        };
+        assert!(matches(&label, EmotionPrediction::new(0.8, 0.0)));
+        assert!(!matches(&label, EmotionPrediction::new(-0.8, 0.0)));
+    }
+
+    #[test]
+    fn test_threshold_condition() {
+        let hostile = TriggerCondition::Threshold {
+            axis: TriggerAxis::Valence,
+            above: None,
+            below: Some(-0.5),
+        };
+        assert!(matches(&hostile, EmotionPrediction::new(-0.6, 0.0)));
+        assert!(!matches(&hostile, EmotionPrediction::new(-0.4, 0.0)));
+
+        let dominant = TriggerCondition::Threshold {
+            axis: TriggerAxis::Dominance,
+            above: Some(0.5),
+            below: None,
+        };
+        assert!(!matches(&dominant, EmotionPrediction::new(0.0, 0.0)));
+        assert!(matches(
+            &dominant,
+            EmotionPrediction::new(0.0, 0.0).with_dominance(Some(0.7))
+        ));
+    }
+
+    #[test]
+    fn test_trigger_from_json() {
+        let trigger: Trigger = serde_json::from_str(
+            r#"{"id": "hostile", "source_id": "player", "type": "threshold", "axis": "valence", "below": -0.5}"#,
+        )
+        .unwrap();
+
+        assert_eq!(trigger.source_id, Some("player".to_string()));
+        assert!(trigger.validate().is_ok());
+
+        let invalid: Trigger =
+            serde_json::from_str(r#"{"id": "broken", "type": "threshold", "axis": "arousal"}"#).unwrap();
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_unmatchable_conditions_are_rejected() {
+        let band = |above, below| TriggerCondition::Threshold {
+            axis: TriggerAxis::Valence,
+            above: Some(above),
+            below: Some(below),
+        };
+        assert!(band(-0.2, 0.2).validate().is_ok());
+        assert!(band(0.2, -0.2).validate().is_err());
+        assert!(band(0.2, 0.2).validate().is_err());
+        assert!(band(f32::NAN, 0.2).validate().is_err());
+
+        let region = TriggerCondition::Region {
+            valence: f32::NAN,
+            arousal: 0.0,
+            radius: 0.3,
+        };
+        assert!(region.validate().is_err());
+    }
+}
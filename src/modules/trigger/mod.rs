@@ -0,0 +1,5 @@
+pub mod condition;
+pub mod store;
+
+pub use condition::{Trigger, TriggerAxis, TriggerCondition};
+pub use store::{EmotionEvent, EmotionEventKind, TriggerStore};
@@ -0,0 +1,170 @@
+use crate::api::types::NpcId;
+use crate::{EmotionPrediction, GameTime, Trigger};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+lazy_static! {
+    static ref NPC_TRIGGERS: Mutex<HashMap<NpcId, Vec<TriggerEntry>>> = Mutex::new(HashMap::new());
+    static ref EMOTION_EVENTS: Mutex<VecDeque<EmotionEvent>> = Mutex::new(VecDeque::new());
+}
+
+/// Events kept until the game polls them, so a game that never polls does not grow memory forever.
+pub const MAX_QUEUED_EVENTS: usize = 1024;
+
+#[derive(Debug, Clone)]
+struct TriggerEntry {
+    trigger: Trigger,
+    active: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmotionEventKind {
+    Fired,
+    Cleared,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmotionEvent {
+    pub npc_id: NpcId,
+    pub trigger_id: String,
+    pub kind: EmotionEventKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    pub emotion: EmotionPrediction,
+    pub label: String,
+    pub game_time: GameTime,
+}
+
+/// Triggers registered per NPC, and the world-wide queue of the events they raise.
+pub struct TriggerStore;
+
+impl TriggerStore {
+    /// Registers a trigger, replacing any trigger of the NPC with the same id. New triggers start inactive.
+    pub fn register(npc_id: &NpcId, trigger: Trigger) -> Result<(), String> {
+        trigger.validate()?;
+
+        let mut npc_triggers = NPC_TRIGGERS.lock().map_err(|_| "Failed to acquire lock")?;
+        let triggers = npc_triggers.entry(npc_id.clone()).or_default();
+
+        triggers.retain(|entry| entry.trigger.id != trigger.id);
+        triggers.push(TriggerEntry { trigger, active: false });
+
+        Ok(())
+    }
+
+    pub fn remove(npc_id: &NpcId, trigger_id: &str) -> Result<bool, String> {
+        let mut npc_triggers = NPC_TRIGGERS.lock().map_err(|_| "Failed to acquire lock")?;
+
+        Ok(npc_triggers.get_mut(npc_id).is_some_and(|triggers| {
+            let count = triggers.len();
+            triggers.retain(|entry| entry.trigger.id != trigger_id);
+            triggers.len() != count
+        }))
+    }
+
+    pub fn get_all(npc_id: &NpcId) -> Result<Vec<Trigger>, String> {
+        let npc_triggers = NPC_TRIGGERS.lock().map_err(|_| "Failed to acquire lock")?;
+
+        Ok(npc_triggers
+            .get(npc_id)
+            .map(|triggers| triggers.iter().map(|entry| entry.trigger.clone()).collect())
+            .unwrap_or_default())
+    }
+
+    /// Records whether the trigger currently matches, returning the event kind when this is a change.
+    pub fn update(npc_id: &NpcId, trigger_id: &str, matched: bool) -> Result<Option<EmotionEventKind>, String> {
+        let mut npc_triggers = NPC_TRIGGERS.lock().map_err(|_| "Failed to acquire lock")?;
+
+        let entry = npc_triggers
+            .get_mut(npc_id)
+            .and_then(|triggers| triggers.iter_mut().find(|entry| entry.trigger.id == trigger_id));
+
+        Ok(match entry {
+            Some(entry) if entry.active != matched => {
+                entry.active = matched;
+                Some(if matched {
+                    EmotionEventKind::Fired
+                } else {
+                    EmotionEventKind::Cleared
+                })
+            }
+            _ => None,
+        })
+    }
+
+    /// Queues an event for `drain_events`. Past `MAX_QUEUED_EVENTS`, the oldest events are dropped.
+    pub fn push_event(event: EmotionEvent) -> Result<(), String> {
+        let mut events = EMOTION_EVENTS.lock().map_err(|_| "Failed to acquire lock")?;
+
+        if events.len() >= MAX_QUEUED_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(event);
+
+        Ok(())
+    }
+
+    /// Removes and returns every queued event, oldest first.
+    pub fn drain_events() -> Result<Vec<EmotionEvent>, String> {
+        let mut events = EMOTION_EVENTS.lock().map_err(|_| "Failed to acquire lock")?;
+
+        Ok(events.drain(..).collect())
+    }
+
+    pub fn remove_npc(npc_id: &NpcId) -> Result<(), String> {
+        NPC_TRIGGERS
+            .lock()
+            .map_err(|_| "Failed to acquire lock")?
+            .remove(npc_id);
+
+        EMOTION_EVENTS
+            .lock()
+            .map_err(|_| "Failed to acquire lock")?
+            .retain(|event| &event.npc_id != npc_id);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EmotionEventKind, TriggerStore};
+    use crate::{Trigger, TriggerCondition};
+
+    fn label_trigger(id: &str) -> Trigger {
+        Trigger {
+            id: id.to_string(),
+            source_id: None,
+            condition: TriggerCondition::Label {
+                label: "angry".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_trigger_transitions() {
+        let npc_id = "trigger-store-transitions".to_string();
+        TriggerStore::register(&npc_id, label_trigger("angry")).unwrap();
+
+        assert_eq!(TriggerStore::update(&npc_id, "angry", false).unwrap(), None);
+        assert_eq!(
+            TriggerStore::update(&npc_id, "angry", true).unwrap(),
+            Some(EmotionEventKind::Fired)
+        );
+        assert_eq!(TriggerStore::update(&npc_id, "angry", true).unwrap(), None);
+        assert_eq!(
+            TriggerStore::update(&npc_id, "angry", false).unwrap(),
+            Some(EmotionEventKind::Cleared)
+        );
+
+        TriggerStore::register(&npc_id, label_trigger("angry")).unwrap();
+        assert_eq!(TriggerStore::get_all(&npc_id).unwrap().len(), 1);
+        assert!(TriggerStore::remove(&npc_id, "angry").unwrap());
+        assert!(!TriggerStore::remove(&npc_id, "angry").unwrap());
+
+        TriggerStore::remove_npc(&npc_id).unwrap();
+    }
+}